@@ -0,0 +1,122 @@
+//! Times parsing and both parts of every day, plus the alternative algorithms some days have.
+//!
+//! Each day reads `day-NN/input.txt` from the usual input location when it exists and falls
+//! back to the example bundled in `benches/inputs`. Day 23 part 2 takes minutes even on the
+//! example, so it only runs with `AOC_BENCH_SLOW` set.
+use aoc_2021::{day04, day05, day14, day22, generate::generator, InputSource, DAYS};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use std::{fs::read_to_string, hint::black_box, path::Path};
+
+const SLOW_DAYS: [u8; 3] = [22, 23, 24];
+
+fn input(day: u8) -> String {
+    read_to_string(InputSource::default_path(day)).unwrap_or_else(|_| {
+        let bundled = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("benches/inputs")
+            .join(format!("day-{:02}.txt", day));
+        read_to_string(bundled).expect("bundled bench input")
+    })
+}
+
+fn slow() -> bool {
+    std::env::var_os("AOC_BENCH_SLOW").is_some()
+}
+
+fn days(c: &mut Criterion) {
+    for day in &DAYS {
+        let input = input(day.day);
+        let mut group = c.benchmark_group(format!("day{:02}", day.day));
+        if SLOW_DAYS.contains(&day.day) {
+            group.sample_size(10);
+        }
+        group.bench_function("parse", |b| b.iter(|| (day.parse)(black_box(&input))));
+        let puzzle = (day.parse)(&input).expect("bench input parses");
+        group.bench_function("part1", |b| b.iter(|| puzzle.part1()));
+        if day.day != 23 || slow() {
+            group.bench_function("part2", |b| b.iter(|| puzzle.part2()));
+        }
+        group.finish();
+    }
+}
+
+fn day04_play(c: &mut Criterion) {
+    // far more boards than a real input, where scanning every cell per number starts to hurt
+    let bingo = generator(4).expect("day 4 generator").generate(1_000, 2021);
+    let parsed = day04::parse(&bingo).expect("generated input parses");
+    let mut group = c.benchmark_group("day04 1000 boards");
+    group.sample_size(10);
+    group.bench_function("play_scanning", |b| {
+        b.iter(|| day04::play_scanning(black_box(&parsed), &day04::ROWS_AND_COLUMNS))
+    });
+    group.bench_function("play", |b| {
+        b.iter(|| day04::play(black_box(&parsed), &day04::ROWS_AND_COLUMNS))
+    });
+    group.finish();
+}
+
+fn day05_overlaps(c: &mut Criterion) {
+    // ten times a real input's lines, so thousands of crossings on every row of the diagram
+    let vents = generator(5).expect("day 5 generator").generate(5_000, 2021);
+    let parsed = day05::parse(&vents).expect("generated input parses");
+    let mut group = c.benchmark_group("day05 5000 lines");
+    group.sample_size(10);
+    group.bench_function("lines_overlaped", |b| {
+        b.iter(|| day05::lines_overlaped(black_box(&parsed), day05::Line::diagonal))
+    });
+    group.bench_function("lines_overlaped_sweep", |b| {
+        b.iter(|| day05::lines_overlaped_sweep(black_box(&parsed), day05::Line::diagonal))
+    });
+    group.finish();
+}
+
+fn day14_steps(c: &mut Criterion) {
+    let polymers = day14::parse(&input(14)).expect("bench input parses");
+    let mut group = c.benchmark_group("day14 10 steps");
+    group.bench_function("step_naive", |b| {
+        b.iter_batched(
+            || polymers.clone(),
+            |mut polymers| {
+                (0..10).for_each(|_| polymers.step_naive());
+                day14::polymers_stat_naive(&polymers)
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("step_counting_pairs", |b| {
+        b.iter_batched(
+            || polymers.clone(),
+            |mut polymers| {
+                for _ in 0..10 {
+                    polymers.step_counting_pairs().expect("counts fit");
+                }
+                day14::polymers_stat_counting(&polymers).expect("counts fit")
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+fn day22_reboot(c: &mut Criterion) {
+    let parsed = day22::parse(&input(22)).expect("bench input parses");
+    let steps = day22::init_region_steps(&parsed.steps);
+    let mut group = c.benchmark_group("day22 init region");
+    group.sample_size(10);
+    group.bench_function("reboot_seq_naive", |b| {
+        b.iter(|| day22::reboot_seq_naive(black_box(&steps)).len())
+    });
+    group.bench_function("reboot_seq", |b| {
+        b.iter(|| day22::reboot_seq(black_box(&steps)))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    days,
+    day04_play,
+    day05_overlaps,
+    day14_steps,
+    day22_reboot
+);
+criterion_main!(benches);
@@ -1,60 +1,39 @@
-use std::fs::read_to_string;
+use aoc_2021::{
+    day01::{parse, Day01, SonarReport},
+    Day, InputSource,
+};
+use clap::{Parser, ValueEnum};
+use std::{io, path::PathBuf};
 
-use itertools::Itertools;
-
-fn parse(input: &str) -> Vec<u32> {
-    input
-        .trim()
-        .lines()
-        .map(|n| n.parse::<u32>())
-        .map(Result::unwrap)
-        .collect()
-}
-fn count_increased(depths: &[u32]) -> usize {
-    depths
-        .iter()
-        .tuple_windows()
-        .filter(|(n1, n2)| n2 > n1)
-        .count()
-}
-fn sliding_windows(depths: &[u32]) -> usize {
-    depths
-        .iter()
-        .tuple_windows()
-        .map(|(n1, n2, n3)| n3 + n2 + n1)
-        .tuple_windows()
-        .filter(|(n1, n2)| n2 > n1)
-        .count()
-}
-fn main() {
-    let input = read_to_string("day-01/input.txt").unwrap();
-    let depths = parse(&input);
-    let part1 = count_increased(&depths);
-    let part2 = sliding_windows(&depths);
-    println!("part1 result is {}", part1);
-    println!("part2 result is {}", part2);
+#[derive(Parser)]
+#[command(about = "Day 1: Sonar Sweep, and a depth profile analyser")]
+struct Args {
+    /// Input file, `-` for stdin [default: day-01/input.txt under $AOC_INPUT_DIR or the crate root]
+    input: Option<PathBuf>,
+    /// Print a report on the depth profile instead of the puzzle answers
+    #[arg(long, value_enum)]
+    report: Option<ReportFormat>,
+    /// Depths per sliding window in the report
+    #[arg(long, default_value_t = 3, requires = "report")]
+    window: usize,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+#[derive(Clone, Copy, ValueEnum)]
+enum ReportFormat {
+    Table,
+    Json,
+}
 
-    const INPUT: &str = r#"199
-200
-208
-210
-200
-207
-240
-269
-260
-263"#;
-    #[test]
-    fn part1() {
-        assert_eq!(count_increased(&parse(INPUT)), 7);
-    }
-    #[test]
-    fn part2() {
-        assert_eq!(sliding_windows(&parse(INPUT)), 5);
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let source = InputSource::from_arg(args.input);
+    let Some(format) = args.report else {
+        return Day::of::<Day01>().run(&source, None);
+    };
+    let report = SonarReport::new(&parse(&source.read(1)?)?, args.window);
+    match format {
+        ReportFormat::Table => print!("{}", report),
+        ReportFormat::Json => report.write_json(&mut io::stdout().lock())?,
     }
+    Ok(())
 }
@@ -1,74 +1,26 @@
-use std::fs::read_to_string;
+use aoc_2021::{
+    day02::{parse, write_csv, Day02, Model},
+    Day, InputSource,
+};
+use clap::Parser;
+use std::{io, path::PathBuf};
 
-//use itertools::Itertools;
-enum Command {
-    Forward(u8),
-    Up(u8),
-    Down(u8),
+#[derive(Parser)]
+#[command(about = "Day 2: Dive!, and a submarine trajectory tracer")]
+struct Args {
+    /// Input file, `-` for stdin [default: day-02/input.txt under $AOC_INPUT_DIR or the crate root]
+    input: Option<PathBuf>,
+    /// Print the state after every command in this model as CSV instead of the puzzle answers
+    #[arg(long, value_enum)]
+    trace: Option<Model>,
 }
 
-fn parse(input: &str) -> Vec<Command> {
-    input
-        .trim()
-        .lines()
-        .map(|line| match line.split_once(' ').unwrap() {
-            ("forward", x) => Command::Forward(x.parse().unwrap_or_default()),
-            ("up", x) => Command::Up(x.parse().unwrap_or_default()),
-            ("down", x) => Command::Down(x.parse().unwrap_or_default()),
-            _ => unreachable!(),
-        })
-        .collect()
-}
-fn distance(commands: &[Command]) -> u32 {
-    let (pos, depth) = commands
-        .iter()
-        .fold((0u32, 0u32), |(pos, depth), command| match command {
-            Command::Forward(units) => (pos + *units as u32, depth),
-            Command::Up(units) => (pos, depth - *units as u32),
-            Command::Down(units) => (pos, depth + *units as u32),
-        });
-    pos * depth
-}
-fn distance_with_aim(commands: &[Command]) -> u32 {
-    let (pos, depth, _) =
-        commands.iter().fold(
-            (0u32, 0u32, 0u32),
-            |(pos, depth, aim), command| match *command {
-                Command::Forward(units) => (pos + units as u32, depth + aim * units as u32, aim),
-                Command::Up(units) => (pos, depth, aim - units as u32),
-                Command::Down(units) => (pos, depth, aim + units as u32),
-            },
-        );
-    pos * depth
-}
-fn main() {
-    let input = read_to_string("day-02/input.txt").unwrap();
-    let commands = parse(&input);
-    let part1 = distance(&commands);
-    let part2 = distance_with_aim(&commands);
-    println!("part1 result is {}", part1);
-    println!("part2 result is {}", part2);
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const INPUT: &str = r#"
-forward 5
-down 5
-forward 8
-up 3
-down 8
-forward 2"#;
-    #[test]
-    fn part1() {
-        let commands = parse(INPUT);
-        assert_eq!(distance(&commands), 150);
-    }
-    #[test]
-    fn part2() {
-        let commands = parse(INPUT);
-        assert_eq!(distance_with_aim(&commands), 900);
-    }
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let source = InputSource::from_arg(args.input);
+    let Some(model) = args.trace else {
+        return Day::of::<Day02>().run(&source, None);
+    };
+    let commands = parse(&source.read(2)?)?;
+    write_csv(&mut io::stdout().lock(), &commands, model)
 }
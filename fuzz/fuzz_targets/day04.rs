@@ -0,0 +1,8 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Some(day) = aoc_2021::day(4) {
+        let _ = (day.parse)(input);
+    }
+});
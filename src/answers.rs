@@ -0,0 +1,132 @@
+use crate::{
+    parsing::{line, parse_all, ParseError},
+    InputSource, Record,
+};
+use anyhow::Context;
+use nom::{
+    branch::alt,
+    bytes::complete::{is_not, tag},
+    character::complete::{char, line_ending, not_line_ending, u8},
+    combinator::{cut, map, opt},
+    multi::{many0, separated_list0},
+    sequence::{delimited, preceded, tuple},
+    Parser,
+};
+use std::{
+    collections::BTreeMap,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+/// Name of the answers file, next to the `day-NN` input directories.
+pub const ANSWERS_FILE: &str = "answers.csv";
+
+/// Known answers keyed by day and part.
+///
+/// Stored as CSV with a `day,part,answer` header, so the output of `run --all --format csv`
+/// is a valid answers file; any columns after `answer` are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers(BTreeMap<(u8, u8), String>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+impl Answers {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let quoted = delimited(
+            char('"'),
+            many0(alt((is_not("\""), tag("\"\"").map(|_| "\"")))),
+            cut(char('"')),
+        )
+        .map(|parts| parts.concat());
+        let bare =
+            opt(is_not(",\r\n")).map(|field: Option<&str>| field.unwrap_or_default().to_owned());
+        let record = tuple((
+            u8,
+            preceded(cut(char(',')), cut(u8)),
+            preceded(cut(char(',')), alt((quoted, bare))),
+            opt(preceded(char(','), not_line_ending)),
+        ))
+        .map(|(day, part, answer, _)| ((day, part), answer));
+        let header = line("header", tag("day,part,answer").and(not_line_ending));
+        let records = separated_list0(line_ending, line("answer", record));
+        let parser = map(
+            tuple((header, opt(line_ending), records)),
+            |(_, _, records)| Self(records.into_iter().collect()),
+        );
+        parse_all(input, parser)
+    }
+    /// The answers file next to the default inputs, see [`InputSource::input_dir`].
+    pub fn default_path() -> PathBuf {
+        InputSource::input_dir().join(ANSWERS_FILE)
+    }
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let input = read_to_string(path)
+            .with_context(|| format!("failed to read answers file {}", path.display()))?;
+        Self::parse(&input).with_context(|| format!("invalid answers file {}", path.display()))
+    }
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+    pub fn check(&self, record: &Record) -> Verdict {
+        match self.get(record.day, record.part) {
+            None => Verdict::Missing,
+            Some(expected) if expected == record.answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_owned(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::write_csv;
+    use std::time::Duration;
+
+    #[test]
+    fn parse() -> anyhow::Result<()> {
+        let answers = Answers::parse("day,part,answer\n17,1,4095\n13,2,\"\n#.\n\"\"x\"\"\",1,2\n")?;
+        assert_eq!(answers.get(17, 1), Some("4095"));
+        assert_eq!(answers.get(13, 2), Some("\n#.\n\"x\""));
+        assert_eq!(answers.get(17, 2), None);
+
+        let err = Answers::parse("day,part,answer\n17;1,4095").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        Ok(())
+    }
+    #[test]
+    fn round_trip() -> anyhow::Result<()> {
+        let record = |part, answer: &str| Record {
+            day: 13,
+            part,
+            answer: answer.to_owned(),
+            parse_time: Duration::ZERO,
+            solve_time: Duration::ZERO,
+        };
+        let records = [record(1, "17"), record(2, "\n#..#\n,\"")];
+        let mut csv = Vec::new();
+        write_csv(&mut csv, &records)?;
+        let answers = Answers::parse(&String::from_utf8(csv)?)?;
+        assert!(records.iter().all(|r| answers.check(r) == Verdict::Pass));
+        assert_eq!(
+            answers.check(&Record {
+                day: 1,
+                ..record(1, "1")
+            }),
+            Verdict::Missing
+        );
+        assert_eq!(
+            answers.check(&record(1, "18")),
+            Verdict::Fail {
+                expected: "17".to_owned()
+            }
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,569 @@
+use crate::{
+    parsing::{line, parse_all},
+    Solution,
+};
+use nom::{
+    character::complete::{line_ending, multispace0, u32},
+    multi::separated_list1,
+    sequence::preceded,
+};
+use std::{
+    collections::VecDeque,
+    fmt,
+    io::{self, BufRead, Write},
+};
+
+pub fn parse(input: &str) -> anyhow::Result<Vec<u32>> {
+    let depths = separated_list1(line_ending, line("depth", u32));
+    Ok(parse_all(input, preceded(multispace0, depths))?)
+}
+/// Parses depths one line at a time, for logs too big to hold in memory; blank lines are skipped.
+pub fn read_depths(reader: impl BufRead) -> impl Iterator<Item = anyhow::Result<u32>> {
+    reader
+        .lines()
+        .enumerate()
+        .filter(|(_, text)| text.as_ref().map_or(true, |text| !text.trim().is_empty()))
+        .map(|(i, text)| {
+            let depth = parse_all(text?.trim(), line("depth", u32)).map_err(|mut e| {
+                e.line = i + 1;
+                e
+            })?;
+            Ok(depth)
+        })
+}
+
+/// Indices of the windows of `size` depths deeper than the window just before them.
+///
+/// Two neighbouring windows share all but their first and last depth, so only those two are
+/// compared; the iterator keeps the last `size` depths and nothing else.
+pub fn increases_with_window<I>(depths: I, size: usize) -> WindowIncreases<I::IntoIter>
+where
+    I: IntoIterator<Item = u32>,
+{
+    WindowIncreases {
+        depths: depths.into_iter(),
+        window: VecDeque::new(),
+        size,
+        index: 0,
+    }
+}
+pub struct WindowIncreases<I> {
+    depths: I,
+    window: VecDeque<u32>,
+    size: usize,
+    /// Index of the first depth in `window`
+    index: usize,
+}
+impl<I: Iterator<Item = u32>> WindowIncreases<I> {
+    /// Groups the increases into runs of consecutive windows.
+    pub fn runs(self) -> Runs<Self> {
+        Runs {
+            indices: self,
+            run: None,
+        }
+    }
+}
+impl<I: Iterator<Item = u32>> Iterator for WindowIncreases<I> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.size == 0 {
+            return None;
+        }
+        for depth in self.depths.by_ref() {
+            if self.window.len() < self.size {
+                self.window.push_back(depth);
+                continue;
+            }
+            let oldest = self.window.pop_front()?;
+            self.window.push_back(depth);
+            self.index += 1;
+            if depth > oldest {
+                return Some(self.index);
+            }
+        }
+        None
+    }
+}
+
+/// `len` consecutive windows from `start` on, each deeper than the one before.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Run {
+    pub start: usize,
+    pub len: usize,
+}
+pub struct Runs<I> {
+    indices: I,
+    run: Option<Run>,
+}
+impl<I: Iterator<Item = usize>> Iterator for Runs<I> {
+    type Item = Run;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for index in self.indices.by_ref() {
+            match &mut self.run {
+                Some(run) if run.start + run.len == index => run.len += 1,
+                run => {
+                    if let Some(done) = run.replace(Run {
+                        start: index,
+                        len: 1,
+                    }) {
+                        return Some(done);
+                    }
+                }
+            }
+        }
+        self.run.take()
+    }
+}
+
+pub fn count_increased(depths: &[u32]) -> usize {
+    increases_with_window(depths.iter().copied(), 1).count()
+}
+pub fn sliding_windows(depths: &[u32]) -> usize {
+    increases_with_window(depths.iter().copied(), 3).count()
+}
+
+/// Consecutive depths from `start` on, each strictly deeper (or shallower) than the last.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stretch {
+    pub start: usize,
+    /// Number of depths, at least 2
+    pub len: usize,
+    pub from: u32,
+    pub to: u32,
+}
+/// The step from depth `index - 1` to depth `index`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Jump {
+    pub index: usize,
+    pub from: u32,
+    pub to: u32,
+}
+impl Jump {
+    pub fn delta(&self) -> i64 {
+        self.to as i64 - self.from as i64
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindowStats {
+    pub start: usize,
+    pub min: u32,
+    pub max: u32,
+    pub mean: f64,
+}
+/// `len` equal depths in a row, at least 2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Plateau {
+    pub start: usize,
+    pub len: usize,
+    pub depth: u32,
+}
+
+/// A depth profile's shape: its trends, biggest steps, windowed statistics and flat stretches.
+///
+/// Where several candidates tie, the earliest one wins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SonarReport {
+    pub depths: usize,
+    pub window: usize,
+    pub longest_increase: Option<Stretch>,
+    pub longest_decrease: Option<Stretch>,
+    pub largest_rise: Option<Jump>,
+    pub largest_drop: Option<Jump>,
+    /// One entry per window of `window` depths; empty if there are fewer depths than that
+    pub windows: Vec<WindowStats>,
+    pub plateaus: Vec<Plateau>,
+}
+
+impl SonarReport {
+    pub fn new(depths: &[u32], window: usize) -> Self {
+        let jumps = || {
+            depths.windows(2).enumerate().map(|(i, pair)| Jump {
+                index: i + 1,
+                from: pair[0],
+                to: pair[1],
+            })
+        };
+        let rise =
+            jumps()
+                .filter(|j| j.delta() > 0)
+                .reduce(|a, b| if b.delta() > a.delta() { b } else { a });
+        let drop =
+            jumps()
+                .filter(|j| j.delta() < 0)
+                .reduce(|a, b| if b.delta() < a.delta() { b } else { a });
+        let windows = if window == 0 {
+            Vec::new()
+        } else {
+            depths
+                .windows(window)
+                .enumerate()
+                .map(|(start, w)| WindowStats {
+                    start,
+                    min: *w.iter().min().expect("window is not empty"),
+                    max: *w.iter().max().expect("window is not empty"),
+                    mean: w.iter().map(|&d| d as u64).sum::<u64>() as f64 / window as f64,
+                })
+                .collect()
+        };
+        let plateaus = groups(depths, |a, b| a == b)
+            .map(|(start, len)| Plateau {
+                start,
+                len,
+                depth: depths[start],
+            })
+            .collect();
+        SonarReport {
+            depths: depths.len(),
+            window,
+            longest_increase: longest(depths, |a, b| b > a),
+            longest_decrease: longest(depths, |a, b| b < a),
+            largest_rise: rise,
+            largest_drop: drop,
+            windows,
+            plateaus,
+        }
+    }
+
+    /// Writes the report as a single JSON object.
+    pub fn write_json(&self, out: &mut impl Write) -> io::Result<()> {
+        fn or_null<T>(value: Option<T>, f: impl Fn(T) -> String) -> String {
+            value.map_or_else(|| "null".to_owned(), f)
+        }
+        let stretch = |s: Stretch| {
+            format!(
+                "{{\"start\": {}, \"len\": {}, \"from\": {}, \"to\": {}}}",
+                s.start, s.len, s.from, s.to
+            )
+        };
+        let jump = |j: Jump| {
+            format!(
+                "{{\"index\": {}, \"from\": {}, \"to\": {}, \"delta\": {}}}",
+                j.index,
+                j.from,
+                j.to,
+                j.delta()
+            )
+        };
+        writeln!(out, "{{")?;
+        writeln!(out, "  \"depths\": {},", self.depths)?;
+        writeln!(out, "  \"window\": {},", self.window)?;
+        writeln!(
+            out,
+            "  \"longest_increase\": {},",
+            or_null(self.longest_increase, stretch)
+        )?;
+        writeln!(
+            out,
+            "  \"longest_decrease\": {},",
+            or_null(self.longest_decrease, stretch)
+        )?;
+        writeln!(
+            out,
+            "  \"largest_rise\": {},",
+            or_null(self.largest_rise, jump)
+        )?;
+        writeln!(
+            out,
+            "  \"largest_drop\": {},",
+            or_null(self.largest_drop, jump)
+        )?;
+        let windows = self.windows.iter().map(|w| {
+            format!(
+                "{{\"start\": {}, \"min\": {}, \"max\": {}, \"mean\": {}}}",
+                w.start, w.min, w.max, w.mean
+            )
+        });
+        write_json_array(out, "windows", windows, ",")?;
+        let plateaus = self.plateaus.iter().map(|p| {
+            format!(
+                "{{\"start\": {}, \"len\": {}, \"depth\": {}}}",
+                p.start, p.len, p.depth
+            )
+        });
+        write_json_array(out, "plateaus", plateaus, "")?;
+        writeln!(out, "}}")
+    }
+}
+
+fn write_json_array(
+    out: &mut impl Write,
+    key: &str,
+    items: impl ExactSizeIterator<Item = String>,
+    separator: &str,
+) -> io::Result<()> {
+    if items.len() == 0 {
+        return writeln!(out, "  \"{}\": []{}", key, separator);
+    }
+    writeln!(out, "  \"{}\": [", key)?;
+    let len = items.len();
+    for (i, item) in items.enumerate() {
+        writeln!(out, "    {}{}", item, if i + 1 < len { "," } else { "" })?;
+    }
+    writeln!(out, "  ]{}", separator)
+}
+
+/// Start and length of each maximal run of two or more depths where every neighbouring pair
+/// satisfies `step`.
+fn groups<'a>(
+    depths: &'a [u32],
+    step: impl Fn(u32, u32) -> bool + 'a,
+) -> impl Iterator<Item = (usize, usize)> + 'a {
+    let mut start = 0;
+    (1..=depths.len()).filter_map(move |i| {
+        if i < depths.len() && step(depths[i - 1], depths[i]) {
+            return None;
+        }
+        let group = (start, i - start);
+        start = i;
+        (group.1 >= 2).then_some(group)
+    })
+}
+
+fn longest(depths: &[u32], step: impl Fn(u32, u32) -> bool) -> Option<Stretch> {
+    groups(depths, step)
+        .reduce(|a, b| if b.1 > a.1 { b } else { a })
+        .map(|(start, len)| Stretch {
+            start,
+            len,
+            from: depths[start],
+            to: depths[start + len - 1],
+        })
+}
+
+/// The report as a table, one row per window at the end.
+impl fmt::Display for SonarReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stretch = |s: Option<Stretch>| {
+            s.map_or_else(
+                || "none".to_owned(),
+                |s| format!("{} depths from #{}: {} -> {}", s.len, s.start, s.from, s.to),
+            )
+        };
+        let jump = |j: Option<Jump>| {
+            j.map_or_else(
+                || "none".to_owned(),
+                |j| format!("{:+} at #{}: {} -> {}", j.delta(), j.index, j.from, j.to),
+            )
+        };
+        writeln!(f, "{:<18}{}", "depths", self.depths)?;
+        writeln!(
+            f,
+            "{:<18}{}",
+            "longest increase",
+            stretch(self.longest_increase)
+        )?;
+        writeln!(
+            f,
+            "{:<18}{}",
+            "longest decrease",
+            stretch(self.longest_decrease)
+        )?;
+        writeln!(f, "{:<18}{}", "largest rise", jump(self.largest_rise))?;
+        writeln!(f, "{:<18}{}", "largest drop", jump(self.largest_drop))?;
+        if self.plateaus.is_empty() {
+            writeln!(f, "{:<18}none", "plateaus")?;
+        }
+        for (i, p) in self.plateaus.iter().enumerate() {
+            let label = if i == 0 { "plateaus" } else { "" };
+            writeln!(
+                f,
+                "{:<18}{} depths of {} from #{}",
+                label, p.len, p.depth, p.start
+            )?;
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:>8}  {:>10}  {:>10}  {:>12}",
+            format!("window {}", self.window),
+            "min",
+            "max",
+            "mean"
+        )?;
+        for w in &self.windows {
+            writeln!(
+                f,
+                "{:>8}  {:>10}  {:>10}  {:>12.2}",
+                format!("#{}", w.start),
+                w.min,
+                w.max,
+                w.mean
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Parsed = Vec<u32>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        Ok(count_increased(parsed))
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        Ok(sliding_windows(parsed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn round_trip(depths in prop::collection::vec(any::<u32>(), 1..50)) {
+            let text = depths.iter().join("\n");
+            prop_assert_eq!(parse(&text).ok(), Some(depths.clone()));
+            let streamed = read_depths(text.as_bytes()).collect::<anyhow::Result<Vec<_>>>();
+            prop_assert_eq!(streamed.ok(), Some(depths));
+        }
+    }
+
+    const INPUT: &str = r#"199
+200
+208
+210
+200
+207
+240
+269
+260
+263"#;
+    #[test]
+    fn part1() {
+        assert_eq!(count_increased(&parse(INPUT).unwrap()), 7);
+    }
+    #[test]
+    fn part2() {
+        assert_eq!(sliding_windows(&parse(INPUT).unwrap()), 5);
+    }
+    #[test]
+    fn windows() -> anyhow::Result<()> {
+        let depths = parse(INPUT)?;
+        let increases = |size| increases_with_window(depths.iter().copied(), size);
+        assert!(increases(1).eq([1, 2, 3, 5, 6, 7, 9]));
+        assert!(increases(3).eq([1, 4, 5, 6, 7]));
+        assert_eq!(increases(0).count(), 0);
+        assert_eq!(increases(10).count(), 0);
+        assert_eq!(increases(usize::MAX).count(), 0);
+        let run = |start, len| Run { start, len };
+        assert!(increases(1).runs().eq([run(1, 3), run(5, 3), run(9, 1)]));
+        assert!(increases(3).runs().eq([run(1, 1), run(4, 4)]));
+
+        // a sawtooth far longer than anything worth collecting
+        let saw = (0..10_000_000u32).map(|i| i % 7);
+        assert_eq!(
+            increases_with_window(saw, 2).count(),
+            10_000_000 / 7 * 5 + 1
+        );
+        Ok(())
+    }
+    #[test]
+    fn streaming() {
+        let depths: Vec<_> = read_depths(INPUT.as_bytes())
+            .collect::<anyhow::Result<_>>()
+            .unwrap();
+        assert_eq!(depths, parse(INPUT).unwrap());
+        let err = read_depths("1\n\n2\nx3".as_bytes())
+            .find_map(Result::err)
+            .unwrap();
+        let err = err.downcast_ref::<crate::parsing::ParseError>().unwrap();
+        assert_eq!((err.line, err.column), (4, 1));
+    }
+    #[test]
+    fn report() -> anyhow::Result<()> {
+        let report = SonarReport::new(&parse(INPUT)?, 3);
+        let stretch = |start, len, from, to| {
+            Some(Stretch {
+                start,
+                len,
+                from,
+                to,
+            })
+        };
+        assert_eq!(report.longest_increase, stretch(0, 4, 199, 210));
+        assert_eq!(report.longest_decrease, stretch(3, 2, 210, 200));
+        let jump = |index, from, to| Some(Jump { index, from, to });
+        assert_eq!(report.largest_rise, jump(6, 207, 240));
+        assert_eq!(report.largest_drop, jump(4, 210, 200));
+        assert_eq!(report.windows.len(), 8);
+        assert_eq!(
+            report.windows[7],
+            WindowStats {
+                start: 7,
+                min: 260,
+                max: 269,
+                mean: 264.0
+            }
+        );
+        assert!(report.plateaus.is_empty());
+
+        let report = SonarReport::new(&[5, 5, 5, 3, 4, 4, 9, 1], 0);
+        let plateau = |start, len, depth| Plateau { start, len, depth };
+        assert_eq!(report.plateaus, [plateau(0, 3, 5), plateau(4, 2, 4)]);
+        assert_eq!(report.longest_increase, stretch(3, 2, 3, 4));
+        assert_eq!(report.largest_drop, jump(7, 9, 1));
+        assert!(report.windows.is_empty());
+
+        let report = SonarReport::new(&[7], 3);
+        assert_eq!(report.longest_increase, None);
+        assert_eq!(report.largest_rise, None);
+        assert!(report.windows.is_empty() && report.plateaus.is_empty());
+        Ok(())
+    }
+    #[test]
+    fn report_output() -> anyhow::Result<()> {
+        let report = SonarReport::new(&[100, 102, 102, 90], 2);
+        assert_eq!(
+            report.to_string(),
+            "\
+depths            4
+longest increase  2 depths from #0: 100 -> 102
+longest decrease  2 depths from #2: 102 -> 90
+largest rise      +2 at #1: 100 -> 102
+largest drop      -12 at #3: 102 -> 90
+plateaus          2 depths of 102 from #1
+
+window 2         min         max          mean
+      #0         100         102        101.00
+      #1         102         102        102.00
+      #2          90         102         96.00
+"
+        );
+        let mut out = Vec::new();
+        report.write_json(&mut out)?;
+        assert_eq!(
+            String::from_utf8_lossy(&out),
+            r#"{
+  "depths": 4,
+  "window": 2,
+  "longest_increase": {"start": 0, "len": 2, "from": 100, "to": 102},
+  "longest_decrease": {"start": 2, "len": 2, "from": 102, "to": 90},
+  "largest_rise": {"index": 1, "from": 100, "to": 102, "delta": 2},
+  "largest_drop": {"index": 3, "from": 102, "to": 90, "delta": -12},
+  "windows": [
+    {"start": 0, "min": 100, "max": 102, "mean": 101},
+    {"start": 1, "min": 102, "max": 102, "mean": 102},
+    {"start": 2, "min": 90, "max": 102, "mean": 96}
+  ],
+  "plateaus": [
+    {"start": 1, "len": 2, "depth": 102}
+  ]
+}
+"#
+        );
+        Ok(())
+    }
+}
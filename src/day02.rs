@@ -0,0 +1,386 @@
+use crate::{
+    parsing::{line, parse_all},
+    Solution,
+};
+use anyhow::{anyhow, Context};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, i64, line_ending, multispace0, not_line_ending, space0, space1},
+    combinator::{cut, opt},
+    multi::separated_list1,
+    sequence::{preceded, terminated, tuple},
+    Parser,
+};
+use std::{fmt, io::Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Forward(i64),
+    /// Astern: the opposite of `Forward`, aim included
+    Back(i64),
+    Up(i64),
+    Down(i64),
+    /// Levels the submarine out; only the aim model has anything to reset
+    ResetAim,
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Command::Forward(units) => write!(f, "forward {}", units),
+            Command::Back(units) => write!(f, "back {}", units),
+            Command::Up(units) => write!(f, "up {}", units),
+            Command::Down(units) => write!(f, "down {}", units),
+            Command::ResetAim => write!(f, "reset-aim"),
+        }
+    }
+}
+
+/// One command per line. Units are signed, `#` starts a comment and blank lines are skipped.
+pub fn parse(input: &str) -> anyhow::Result<Vec<Command>> {
+    let moves = alt((
+        tag("forward").map(|_| Command::Forward as fn(i64) -> Command),
+        tag("back").map(|_| Command::Back as fn(i64) -> Command),
+        tag("up").map(|_| Command::Up as fn(i64) -> Command),
+        tag("down").map(|_| Command::Down as fn(i64) -> Command),
+    ));
+    let command = alt((
+        tuple((moves, cut(preceded(space1, i64)))).map(|(kind, units)| kind(units)),
+        tag("reset-aim").map(|_| Command::ResetAim),
+    ));
+    let comment = preceded(space0, opt(preceded(char('#'), not_line_ending)));
+    let statement = preceded(space0, terminated(opt(command), comment));
+    let program = separated_list1(line_ending, line("command", statement));
+    let commands = parse_all(input, preceded(multispace0, program))?;
+    Ok(commands.into_iter().flatten().collect())
+}
+
+/// Where the submarine is; `depth` grows downwards from the surface at 0.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct State {
+    pub position: i64,
+    pub depth: i64,
+    pub aim: i64,
+}
+
+/// How commands move the submarine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Model {
+    /// `up` and `down` change the depth directly
+    Plain,
+    /// `up` and `down` tilt the submarine, moving it changes the depth
+    Aim,
+}
+
+/// The value a command pushed out of range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    Position,
+    Depth,
+    Aim,
+    /// The command's own units, when it has to negate them
+    Units,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            Overflow::Position => "position",
+            Overflow::Depth => "depth",
+            Overflow::Aim => "aim",
+            Overflow::Units => "units",
+        };
+        write!(f, "{} overflowed", value)
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+impl State {
+    /// The state after `command`, or which value overflowed.
+    pub fn step(self, command: Command, model: Model) -> Result<State, Overflow> {
+        let Self {
+            position,
+            depth,
+            aim,
+        } = self;
+        let advance = |units: i64| -> Result<State, Overflow> {
+            Ok(State {
+                position: position.checked_add(units).ok_or(Overflow::Position)?,
+                depth: match model {
+                    Model::Plain => depth,
+                    Model::Aim => aim
+                        .checked_mul(units)
+                        .and_then(|dive| depth.checked_add(dive))
+                        .ok_or(Overflow::Depth)?,
+                },
+                aim,
+            })
+        };
+        let dive = |units: i64| -> Result<State, Overflow> {
+            Ok(match model {
+                Model::Plain => State {
+                    depth: depth.checked_add(units).ok_or(Overflow::Depth)?,
+                    ..self
+                },
+                Model::Aim => State {
+                    aim: aim.checked_add(units).ok_or(Overflow::Aim)?,
+                    ..self
+                },
+            })
+        };
+        let negate = |units: i64| units.checked_neg().ok_or(Overflow::Units);
+        match command {
+            Command::Forward(units) => advance(units),
+            Command::Back(units) => advance(negate(units)?),
+            Command::Down(units) => dive(units),
+            Command::Up(units) => dive(negate(units)?),
+            Command::ResetAim => Ok(State { aim: 0, ..self }),
+        }
+    }
+}
+
+/// The state after every command, numbered from 1; a command that overflows or leaves the water
+/// ends the trajectory with an error.
+pub fn trajectory(commands: &[Command], model: Model) -> Trajectory<'_> {
+    Trajectory {
+        commands: commands.iter().enumerate(),
+        model,
+        state: Some(State::default()),
+    }
+}
+pub struct Trajectory<'c> {
+    commands: std::iter::Enumerate<std::slice::Iter<'c, Command>>,
+    model: Model,
+    /// `None` once a command has failed
+    state: Option<State>,
+}
+impl Iterator for Trajectory<'_> {
+    type Item = anyhow::Result<(usize, State)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let state = self.state.take()?;
+        let (i, &command) = self.commands.next()?;
+        let step = i + 1;
+        let next = match state.step(command, self.model) {
+            Ok(next) => next,
+            Err(overflow) => {
+                return Some(Err(anyhow!(
+                    "{} at command {} `{}`",
+                    overflow,
+                    step,
+                    command
+                )))
+            }
+        };
+        if next.depth < 0 {
+            return Some(Err(anyhow!(
+                "surfaced above sea level at command {} `{}`",
+                step,
+                command
+            )));
+        }
+        self.state = Some(next);
+        Some(Ok((step, next)))
+    }
+}
+
+/// Runs `commands` from the surface, failing if any of them overflows or leaves the water.
+pub fn navigate(commands: &[Command], model: Model) -> anyhow::Result<State> {
+    trajectory(commands, model).try_fold(State::default(), |_, step| Ok(step?.1))
+}
+
+/// Writes the trajectory as CSV with a header line, one row per command. Rows up to a failing
+/// command are written before its error is returned.
+pub fn write_csv(out: &mut impl Write, commands: &[Command], model: Model) -> anyhow::Result<()> {
+    writeln!(out, "step,command,position,depth,aim")?;
+    for step in trajectory(commands, model) {
+        let (step, state) = step?;
+        writeln!(
+            out,
+            "{},{},{},{},{}",
+            step,
+            commands[step - 1],
+            state.position,
+            state.depth,
+            state.aim
+        )?;
+    }
+    Ok(())
+}
+pub fn distance(commands: &[Command]) -> anyhow::Result<i64> {
+    let State {
+        position, depth, ..
+    } = navigate(commands, Model::Plain)?;
+    position.checked_mul(depth).context("answer overflowed")
+}
+pub fn distance_with_aim(commands: &[Command]) -> anyhow::Result<i64> {
+    let State {
+        position, depth, ..
+    } = navigate(commands, Model::Aim)?;
+    position.checked_mul(depth).context("answer overflowed")
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Parsed = Vec<Command>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        distance(parsed)
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        distance_with_aim(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
+    fn command() -> impl Strategy<Value = Command> {
+        let moves: [fn(i64) -> Command; 4] =
+            [Command::Forward, Command::Back, Command::Up, Command::Down];
+        prop_oneof![
+            (prop::sample::select(moves.to_vec()), any::<i64>()).prop_map(|(kind, n)| kind(n)),
+            Just(Command::ResetAim),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(commands in prop::collection::vec(command(), 1..50)) {
+            let text = commands.iter().join("\n");
+            prop_assert_eq!(parse(&text).ok(), Some(commands));
+        }
+    }
+
+    const INPUT: &str = r#"
+forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2"#;
+    #[test]
+    fn part1() {
+        let commands = parse(INPUT).unwrap();
+        assert_eq!(distance(&commands).unwrap(), 150);
+    }
+    #[test]
+    fn part2() {
+        let commands = parse(INPUT).unwrap();
+        assert_eq!(distance_with_aim(&commands).unwrap(), 900);
+    }
+    #[test]
+    fn language() -> anyhow::Result<()> {
+        let program = "# a comment\n\n  forward 5 # ahead\ndown -2\nback 3\n\ndown 4\nreset-aim\nforward 9223372036854775800\n";
+        let commands = parse(program)?;
+        assert_eq!(
+            commands,
+            [
+                Command::Forward(5),
+                Command::Down(-2),
+                Command::Back(3),
+                Command::Down(4),
+                Command::ResetAim,
+                Command::Forward(9_223_372_036_854_775_800),
+            ]
+        );
+        assert_eq!(commands[4].to_string(), "reset-aim");
+        let state = navigate(&commands[..4], Model::Aim)?;
+        assert_eq!(
+            state,
+            State {
+                position: 2,
+                depth: 6,
+                aim: 2
+            }
+        );
+        let err = navigate(&commands, Model::Plain).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "surfaced above sea level at command 2 `down -2`"
+        );
+        let astern = [Command::Down(1), Command::Back(2)];
+        let err = navigate(&astern, Model::Aim).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "surfaced above sea level at command 2 `back 2`"
+        );
+
+        let err = parse("forward 1\nsideways 2").unwrap_err();
+        let err = err.downcast_ref::<crate::parsing::ParseError>().unwrap();
+        assert_eq!((err.line, err.column), (2, 1));
+        let err = parse("forward 1\n\nup 99999999999999999999").unwrap_err();
+        let err = err.downcast_ref::<crate::parsing::ParseError>().unwrap();
+        assert_eq!((err.line, err.column), (3, 4));
+        assert!(parse("reset-aim 3").is_err());
+
+        let far = [Command::Forward(i64::MAX), Command::Forward(1)];
+        let err = navigate(&far, Model::Plain).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "position overflowed at command 2 `forward 1`"
+        );
+        let deep = [Command::Down(i64::MAX), Command::Down(1)];
+        let err = navigate(&deep, Model::Plain).unwrap_err();
+        assert_eq!(err.to_string(), "depth overflowed at command 2 `down 1`");
+        let err = navigate(&[Command::Up(i64::MIN)], Model::Aim).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("units overflowed at command 1 `up {}`", i64::MIN)
+        );
+        let tilted = State::default().step(Command::Down(i64::MAX), Model::Aim)?;
+        assert_eq!(
+            tilted.step(Command::Down(1), Model::Aim),
+            Err(Overflow::Aim)
+        );
+        assert!(distance(&[Command::Up(1)]).is_err());
+        Ok(())
+    }
+    #[test]
+    fn trace() -> anyhow::Result<()> {
+        let commands = parse(INPUT)?;
+        let plain = trajectory(&commands, Model::Plain).collect::<anyhow::Result<Vec<_>>>()?;
+        let aim = trajectory(&commands, Model::Aim).collect::<anyhow::Result<Vec<_>>>()?;
+        assert_eq!(plain.len(), 6);
+        let state = |position, depth, aim| State {
+            position,
+            depth,
+            aim,
+        };
+        assert_eq!(plain[3], (4, state(13, 2, 0)));
+        assert_eq!(aim[3], (4, state(13, 40, 2)));
+        assert_eq!(aim[5], (6, state(15, 60, 10)));
+
+        let mut out = Vec::new();
+        write_csv(&mut out, &commands[..3], Model::Aim)?;
+        assert_eq!(
+            String::from_utf8_lossy(&out),
+            "step,command,position,depth,aim\n1,forward 5,5,0,0\n2,down 5,5,0,5\n3,forward 8,13,40,5\n"
+        );
+
+        let commands = [Command::Down(2), Command::Up(3), Command::Down(1)];
+        let mut steps = trajectory(&commands, Model::Plain);
+        assert_eq!(steps.next().transpose()?, Some((1, state(0, 2, 0))));
+        assert!(steps.next().unwrap().is_err());
+        assert!(steps.next().is_none());
+        let mut out = Vec::new();
+        assert!(write_csv(&mut out, &commands, Model::Plain).is_err());
+        assert_eq!(
+            String::from_utf8_lossy(&out),
+            "step,command,position,depth,aim\n1,down 2,0,2,0\n"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,420 @@
+use crate::{
+    parsing::{line, parse_all, ParseError},
+    Solution,
+};
+use anyhow::Context;
+use bitvec::{slice::BitSlice, vec::BitVec};
+use nom::{
+    bytes::complete::take_while1,
+    character::complete::{line_ending, multispace0},
+    multi::separated_list1,
+    sequence::preceded,
+    Offset,
+};
+use std::{cmp::Ordering, fmt};
+
+/// A diagnostic report stored column by column, one bit per number.
+///
+/// Column 0 holds the most significant bits; the numbers can be any width.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    columns: Vec<BitVec>,
+    len: usize,
+}
+
+impl Report {
+    /// Bits per number
+    pub fn width(&self) -> usize {
+        self.columns.len()
+    }
+    /// Numbers in the report
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Bit `column` of every number, in report order.
+    pub fn column(&self, column: usize) -> &BitSlice {
+        &self.columns[column]
+    }
+    /// Number `row`'s bits, most significant first.
+    pub fn row(&self, row: usize) -> BitVec {
+        self.columns.iter().map(|column| column[row]).collect()
+    }
+    /// Whether bit `column` is set in at least half of the numbers selected by `rows`, which has
+    /// one bit per number.
+    pub fn most_common_bit(&self, column: usize, rows: &BitVec) -> bool {
+        let ones = count_ones_among(&self.columns[column], rows);
+        ones >= rows.count_ones() - ones
+    }
+    /// The most common bit of every column.
+    pub fn gamma(&self) -> BitVec {
+        let all = BitVec::<usize>::repeat(true, self.len);
+        (0..self.width())
+            .map(|column| self.most_common_bit(column, &all))
+            .collect()
+    }
+    /// The least common bit of every column.
+    pub fn epsilon(&self) -> BitVec {
+        !self.gamma()
+    }
+}
+
+/// How many of `column`'s bits are set among the rows set in `rows`, a word at a time.
+fn count_ones_among(column: &BitVec, rows: &BitVec) -> usize {
+    // a column's unused tail bits are cleared when the report is built
+    column
+        .as_raw_slice()
+        .iter()
+        .zip(rows.as_raw_slice())
+        .map(|(column, rows)| (column & rows).count_ones() as usize)
+        .sum()
+}
+
+/// `bits`, most significant first, as a number, if it fits.
+pub fn to_u128(bits: &BitSlice) -> Option<u128> {
+    let bits = &bits[bits.first_one().unwrap_or(bits.len())..];
+    (bits.len() <= u128::BITS as usize)
+        .then(|| bits.iter().fold(0, |acc, bit| acc << 1 | *bit as u128))
+}
+
+/// One diagnostic number per line, most significant bit first; all the same width.
+pub fn parse(input: &str) -> anyhow::Result<Report> {
+    let input = input.trim();
+    let number = line("diagnostic number", take_while1(|c| c == '0' || c == '1'));
+    let numbers = parse_all(
+        input,
+        preceded(multispace0, separated_list1(line_ending, number)),
+    )?;
+    let width = numbers[0].len();
+    let mut columns = vec![BitVec::with_capacity(numbers.len()); width];
+    for number in &numbers {
+        if number.len() != width {
+            let offset = input.offset(number) + width.min(number.len());
+            let expected = format!("expected {} bits", width);
+            return Err(ParseError::at(input, offset, expected, vec!["diagnostic number"]).into());
+        }
+        for (column, bit) in columns.iter_mut().zip(number.bytes()) {
+            column.push(bit == b'1');
+        }
+    }
+    for column in &mut columns {
+        column.set_uninitialized(false);
+    }
+    Ok(Report {
+        columns,
+        len: numbers.len(),
+    })
+}
+
+pub fn power_consumption(report: &Report) -> anyhow::Result<u128> {
+    let gamma = to_u128(&report.gamma());
+    let epsilon = to_u128(&report.epsilon());
+    gamma
+        .zip(epsilon)
+        .and_then(|(gamma, epsilon)| gamma.checked_mul(epsilon))
+        .context("power consumption doesn't fit in 128 bits")
+}
+
+/// Which numbers a [`Criterion`] keeps at each column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Commonness {
+    /// Those with the column's most common bit
+    Most,
+    /// Those with the column's least common bit
+    Least,
+}
+
+/// The order a [`Criterion`] looks at the columns in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnOrder {
+    MostSignificantFirst,
+    LeastSignificantFirst,
+    /// These columns, column 0 being the most significant
+    Custom(Vec<usize>),
+}
+
+/// A bit criterion: which numbers survive each column, and in what order columns are tried.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Criterion {
+    pub keep: Commonness,
+    /// The bit kept when there are as many ones as zeros
+    pub tie: bool,
+    pub order: ColumnOrder,
+}
+
+impl Criterion {
+    /// The oxygen generator rating's criterion: most common bit, ties keep 1.
+    pub fn oxygen() -> Self {
+        Criterion {
+            keep: Commonness::Most,
+            tie: true,
+            order: ColumnOrder::MostSignificantFirst,
+        }
+    }
+    /// The CO2 scrubber rating's criterion: least common bit, ties keep 0.
+    pub fn co2() -> Self {
+        Criterion {
+            keep: Commonness::Least,
+            tie: false,
+            order: ColumnOrder::MostSignificantFirst,
+        }
+    }
+    fn columns(&self, width: usize) -> Vec<usize> {
+        match &self.order {
+            ColumnOrder::MostSignificantFirst => (0..width).collect(),
+            ColumnOrder::LeastSignificantFirst => (0..width).rev().collect(),
+            ColumnOrder::Custom(columns) => columns.clone(),
+        }
+    }
+}
+
+/// What a [`Criterion`] did at one column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elimination {
+    pub column: usize,
+    /// Set bits among the numbers still in the running
+    pub ones: usize,
+    pub zeros: usize,
+    /// The bit the survivors have in this column
+    pub kept: bool,
+    /// Indices of the numbers dropped here, in report order
+    pub eliminated: Vec<usize>,
+}
+
+impl fmt::Display for Elimination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "column {}: {} ones, {} zeros, kept {}, eliminated {}",
+            self.column,
+            self.ones,
+            self.zeros,
+            self.kept as u8,
+            self.eliminated.len()
+        )
+    }
+}
+
+/// The number a [`Criterion`] settled on, and how it got there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rating {
+    /// Index of the number in the report
+    pub row: usize,
+    pub bits: BitVec,
+    /// One entry per column looked at, in the order they were
+    pub trace: Vec<Elimination>,
+}
+
+/// Filters the report's numbers column by column with `criterion` until one is left.
+///
+/// A column where every remaining number has the same bit eliminates nothing, whatever the
+/// criterion. If several identical numbers are still left after the last column, the first wins.
+pub fn rate(report: &Report, criterion: &Criterion) -> anyhow::Result<Rating> {
+    let mut rows = BitVec::<usize>::repeat(true, report.len());
+    rows.set_uninitialized(false);
+    let mut trace = Vec::new();
+    for column in criterion.columns(report.width()) {
+        let remaining = rows.count_ones();
+        if remaining <= 1 {
+            break;
+        }
+        let bits = report.columns.get(column).with_context(|| {
+            format!(
+                "column {} is out of range for {} bit numbers",
+                column,
+                report.width()
+            )
+        })?;
+        let ones = count_ones_among(bits, &rows);
+        let zeros = remaining - ones;
+        let kept = match (ones.cmp(&zeros), criterion.keep) {
+            _ if ones == 0 || zeros == 0 => ones > 0,
+            (Ordering::Equal, _) => criterion.tie,
+            (order, Commonness::Most) => order == Ordering::Greater,
+            (order, Commonness::Least) => order == Ordering::Less,
+        };
+        let eliminated = rows.iter_ones().filter(|&row| bits[row] != kept).collect();
+        for (rows, bits) in rows.as_raw_mut_slice().iter_mut().zip(bits.as_raw_slice()) {
+            *rows &= if kept { *bits } else { !bits };
+        }
+        trace.push(Elimination {
+            column,
+            ones,
+            zeros,
+            kept,
+            eliminated,
+        });
+    }
+    let row = rows.first_one().context("the report is empty")?;
+    Ok(Rating {
+        row,
+        bits: report.row(row),
+        trace,
+    })
+}
+
+pub fn oxygen_co2(report: &Report) -> anyhow::Result<u128> {
+    let oxygen = rate(report, &Criterion::oxygen()).context("no oxygen generator rating")?;
+    let co2 = rate(report, &Criterion::co2()).context("no CO2 scrubber rating")?;
+    to_u128(&oxygen.bits)
+        .zip(to_u128(&co2.bits))
+        .and_then(|(oxygen, co2)| oxygen.checked_mul(co2))
+        .context("life support rating doesn't fit in 128 bits")
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Parsed = Report;
+    type Part1 = u128;
+    type Part2 = u128;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        power_consumption(parsed)
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        oxygen_co2(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn round_trip(rows in (1..150usize).prop_flat_map(|width| {
+            prop::collection::vec(prop::collection::vec(any::<bool>(), width), 1..50)
+        })) {
+            let text = rows
+                .iter()
+                .map(|row| row.iter().map(|&bit| if bit { '1' } else { '0' }).collect::<String>())
+                .collect::<Vec<_>>()
+                .join("\n");
+            let report = parse(&text).unwrap();
+            prop_assert_eq!((report.width(), report.len()), (rows[0].len(), rows.len()));
+            for (i, row) in rows.iter().enumerate() {
+                prop_assert_eq!(report.row(i), row.iter().collect::<BitVec>());
+            }
+        }
+    }
+
+    const INPUT: &str = r#"
+00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010
+    "#;
+    #[test]
+    fn part1() -> anyhow::Result<()> {
+        let report = parse(INPUT)?;
+        assert_eq!(power_consumption(&report).unwrap(), 198);
+        Ok(())
+    }
+    #[test]
+    fn part2() -> anyhow::Result<()> {
+        let report = parse(INPUT)?;
+        assert_eq!(oxygen_co2(&report).unwrap(), 230);
+        Ok(())
+    }
+    #[test]
+    fn packed() -> anyhow::Result<()> {
+        let report = parse(INPUT)?;
+        assert_eq!((report.width(), report.len()), (5, 12));
+        assert_eq!(to_u128(&report.row(1)), Some(0b11110));
+        assert_eq!(to_u128(&report.gamma()), Some(22));
+        assert_eq!(to_u128(&report.epsilon()), Some(9));
+
+        // more rows than a word, so the popcount spans several
+        let rows: Vec<String> = (0..200u32).map(|i| format!("{:08b}", i)).collect();
+        let report = parse(&rows.join("\n"))?;
+        let some: BitVec = (0..200).map(|i| i % 3 == 0).collect();
+        for column in 0..8 {
+            let naive = (0..200)
+                .filter(|&i| some[i] && report.column(column)[i])
+                .count();
+            assert_eq!(
+                report.most_common_bit(column, &some),
+                2 * naive >= some.count_ones()
+            );
+        }
+        Ok(())
+    }
+    #[test]
+    fn wide() -> anyhow::Result<()> {
+        let number = |ones: usize, width: usize| format!("{:0>width$}", "1".repeat(ones));
+        let input = [number(80, 200), number(80, 200), number(40, 200)].join("\n");
+        let report = parse(&input)?;
+        assert_eq!(report.width(), 200);
+        assert_eq!(to_u128(&report.gamma()), Some((1 << 80) - 1));
+        assert!(to_u128(&report.epsilon()).is_none());
+        assert!(power_consumption(&report).is_err());
+        assert_eq!(oxygen_co2(&report)?, ((1 << 80) - 1) * ((1 << 40) - 1));
+
+        let input = [number(128, 128), number(128, 128), number(0, 128)].join("\n");
+        let report = parse(&input)?;
+        assert_eq!(to_u128(&report.gamma()), Some(u128::MAX));
+        assert_eq!(power_consumption(&report)?, 0);
+
+        let err = parse("0101\n011\n1111").unwrap_err();
+        let err = err.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column), (2, 4));
+        assert_eq!(err.expected, "expected 4 bits");
+        Ok(())
+    }
+    #[test]
+    fn criteria() -> anyhow::Result<()> {
+        let report = parse(INPUT)?;
+        let oxygen = rate(&report, &Criterion::oxygen())?;
+        assert_eq!((oxygen.row, to_u128(&oxygen.bits)), (3, Some(23)));
+        let steps: Vec<_> = oxygen.trace.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            steps,
+            [
+                "column 0: 7 ones, 5 zeros, kept 1, eliminated 5",
+                "column 1: 3 ones, 4 zeros, kept 0, eliminated 3",
+                "column 2: 3 ones, 1 zeros, kept 1, eliminated 1",
+                "column 3: 2 ones, 1 zeros, kept 1, eliminated 1",
+                "column 4: 1 ones, 1 zeros, kept 1, eliminated 1",
+            ]
+        );
+        assert_eq!(oxygen.trace[2].eliminated, [8]);
+        let co2 = rate(&report, &Criterion::co2())?;
+        assert_eq!((co2.row, to_u128(&co2.bits)), (11, Some(10)));
+        assert_eq!(co2.trace.len(), 3);
+
+        let lsb_first = Criterion {
+            order: ColumnOrder::LeastSignificantFirst,
+            ..Criterion::oxygen()
+        };
+        let rating = rate(&report, &lsb_first)?;
+        assert_eq!(rating.trace[0].column, 4);
+        assert!(!rating.trace[0].kept);
+        let ties_to_zero = Criterion {
+            tie: false,
+            ..Criterion::oxygen()
+        };
+        assert_eq!(to_u128(&rate(&report, &ties_to_zero)?.bits), Some(22));
+        let custom = Criterion {
+            order: ColumnOrder::Custom(vec![2, 7]),
+            ..Criterion::oxygen()
+        };
+        assert!(rate(&report, &custom).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,577 @@
+use crate::{
+    parsing::{line, parse_all, IResult},
+    Solution,
+};
+use anyhow::{bail, Context};
+use nom::{
+    character::complete::{char, line_ending, space0, space1, u32},
+    combinator::map,
+    error::{context, ErrorKind, ParseError as _, VerboseError},
+    multi::separated_list1,
+    sequence::preceded,
+    Parser,
+};
+use std::{collections::HashMap, fmt, ops::Not};
+
+/// A set of marked numbers that wins a board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pattern {
+    Row,
+    Column,
+    /// Either of the two corner to corner diagonals
+    Diagonal,
+    /// The four corner cells
+    Corners,
+    /// Every cell on the board
+    FullCard,
+}
+
+/// The pattern a board won with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Line {
+    Row(usize),
+    Column(usize),
+    /// Top left to bottom right
+    Diagonal,
+    /// Top right to bottom left
+    AntiDiagonal,
+    Corners,
+    FullCard,
+}
+
+/// The standard rules: a complete row or column wins.
+pub const ROWS_AND_COLUMNS: [Pattern; 2] = [Pattern::Row, Pattern::Column];
+
+/// A square board of any size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Board {
+    size: usize,
+    /// Numbers and whether they are marked, row by row
+    cells: Vec<(u32, bool)>,
+}
+impl Board {
+    pub fn size(&self) -> usize {
+        self.size
+    }
+    fn marked(&self, row: usize, col: usize) -> bool {
+        self.cells[row * self.size + col].1
+    }
+    /// The first of `patterns` that marking `(row, col)` completed; only patterns through that
+    /// cell are checked.
+    fn completed(&self, patterns: &[Pattern], row: usize, col: usize) -> Option<Line> {
+        let last = self.size - 1;
+        patterns.iter().find_map(|pattern| match pattern {
+            Pattern::Row => (0..self.size)
+                .all(|c| self.marked(row, c))
+                .then_some(Line::Row(row)),
+            Pattern::Column => (0..self.size)
+                .all(|r| self.marked(r, col))
+                .then_some(Line::Column(col)),
+            Pattern::Diagonal => {
+                if row == col && (0..self.size).all(|i| self.marked(i, i)) {
+                    Some(Line::Diagonal)
+                } else if row + col == last && (0..self.size).all(|i| self.marked(i, last - i)) {
+                    Some(Line::AntiDiagonal)
+                } else {
+                    None
+                }
+            }
+            Pattern::Corners => ((row == 0 || row == last)
+                && (col == 0 || col == last)
+                && [(0, 0), (0, last), (last, 0), (last, last)]
+                    .iter()
+                    .all(|&(r, c)| self.marked(r, c)))
+            .then_some(Line::Corners),
+            Pattern::FullCard => self
+                .cells
+                .iter()
+                .all(|&(_, marked)| marked)
+                .then_some(Line::FullCard),
+        })
+    }
+    fn sum_unmarked(&self) -> u64 {
+        self.cells
+            .iter()
+            .filter_map(|&(num, marked)| marked.not().then_some(num as u64))
+            .sum::<u64>()
+    }
+    fn score(&self, num: u32) -> anyhow::Result<u64> {
+        self.sum_unmarked()
+            .checked_mul(num as u64)
+            .context("score overflowed")
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Draw {
+    numbers: Vec<u32>,
+}
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParsedInput {
+    draw: Draw,
+    boards: Vec<Board>,
+}
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .cells
+            .iter()
+            .map(|(num, _)| num.to_string().len())
+            .max()
+            .unwrap_or(0);
+        for (i, row) in self.cells.chunks(self.size).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let row = row.iter().map(|(num, _)| format!("{:>1$}", num, width));
+            write!(f, "{}", row.collect::<Vec<_>>().join(" "))?;
+        }
+        Ok(())
+    }
+}
+impl fmt::Display for ParsedInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let draw: Vec<_> = self.draw.numbers.iter().map(u32::to_string).collect();
+        write!(f, "{}", draw.join(","))?;
+        for board in &self.boards {
+            write!(f, "\n\n{}", board)?;
+        }
+        Ok(())
+    }
+}
+
+/// A board: as many rows as the first row has numbers, each of them as wide.
+fn board(input: &str) -> IResult<'_, Board> {
+    let row = line(
+        "board row",
+        preceded(space0, separated_list1(space1, u32.map(|num| (num, false)))),
+    );
+    let (rest, rows) = separated_list1(line_ending, row).parse(input)?;
+    let size = rows[0].len();
+    if let Some(bad) = rows.iter().position(|row| row.len() != size) {
+        let at = input.lines().nth(bad).unwrap_or(input);
+        return Err(nom::Err::Failure(VerboseError::from_error_kind(
+            at,
+            ErrorKind::Count,
+        )));
+    }
+    if rows.len() != size {
+        let at = input.lines().nth(rows.len().min(size)).unwrap_or(rest);
+        return Err(nom::Err::Failure(VerboseError::from_error_kind(
+            at,
+            ErrorKind::Count,
+        )));
+    }
+    let cells = rows.into_iter().flatten().collect();
+    Ok((rest, Board { size, cells }))
+}
+
+pub fn parse(input: &str) -> anyhow::Result<ParsedInput> {
+    let draw =
+        line("draw numbers", separated_list1(char(','), u32)).map(|numbers| Draw { numbers });
+    let gap = |i| line_ending.and(line_ending).parse(i);
+    let boards = separated_list1(gap, context("board", board));
+    let parser = map(draw.and(gap).and(boards), |((draw, _), boards)| {
+        ParsedInput { draw, boards }
+    });
+
+    Ok(parse_all(input, parser)?)
+}
+/// Marks `num` on `board`, returning the pattern it completed, if any.
+fn winner_check(board: &mut Board, patterns: &[Pattern], num: u32) -> Option<Line> {
+    for r in 0..board.size {
+        for c in 0..board.size {
+            let cell = &mut board.cells[r * board.size + c];
+            if cell.0 == num {
+                cell.1 = true;
+                if let Some(line) = board.completed(patterns, r, c) {
+                    return Some(line);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// How a board won.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Win {
+    /// Index of the board in the input
+    pub board: usize,
+    /// Index of the winning number in the draw
+    pub draw: usize,
+    pub number: u32,
+    pub score: u64,
+    pub line: Line,
+}
+
+/// Every board, in the order they won.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ranking {
+    /// Boards winning on the same number are in input order
+    pub winners: Vec<Win>,
+    /// Boards still without a win once every number is drawn
+    pub losers: Vec<usize>,
+}
+impl Ranking {
+    /// The `n`th board to win, counting from 0.
+    pub fn nth(&self, n: usize) -> Option<&Win> {
+        self.winners.get(n)
+    }
+}
+
+/// Same as [`play`], but finds each number by scanning every cell of every board still playing.
+pub fn play_scanning(input: &ParsedInput, patterns: &[Pattern]) -> anyhow::Result<Ranking> {
+    let mut boards = input.boards.clone();
+    let mut playing: Vec<usize> = (0..boards.len()).collect();
+    let mut winners = Vec::new();
+    for (draw, &number) in input.draw.numbers.iter().enumerate() {
+        if playing.is_empty() {
+            break;
+        }
+        let mut result = Ok(());
+        playing.retain(|&board| {
+            let Some(line) = winner_check(&mut boards[board], patterns, number) else {
+                return true;
+            };
+            match boards[board].score(number) {
+                Ok(score) => winners.push(Win {
+                    board,
+                    draw,
+                    number,
+                    score,
+                    line,
+                }),
+                Err(e) => result = Err(e),
+            }
+            false
+        });
+        result?;
+    }
+    Ok(Ranking {
+        winners,
+        losers: playing,
+    })
+}
+
+/// How far one board is from each pattern, counted in marked cells.
+#[derive(Debug, Clone)]
+struct Tally {
+    size: usize,
+    marked: Vec<bool>,
+    rows: Vec<usize>,
+    cols: Vec<usize>,
+    diagonal: usize,
+    anti_diagonal: usize,
+    corners: usize,
+    total: usize,
+    unmarked_sum: u64,
+}
+impl Tally {
+    fn new(board: &Board) -> Self {
+        Tally {
+            size: board.size,
+            marked: vec![false; board.cells.len()],
+            rows: vec![0; board.size],
+            cols: vec![0; board.size],
+            diagonal: 0,
+            anti_diagonal: 0,
+            corners: 0,
+            total: 0,
+            unmarked_sum: board.cells.iter().map(|&(num, _)| num as u64).sum(),
+        }
+    }
+    fn is_corner(&self, row: usize, col: usize) -> bool {
+        let last = self.size - 1;
+        (row == 0 || row == last) && (col == 0 || col == last)
+    }
+    /// Marks `(row, col)` holding `num`, returning the first of `patterns` it completed.
+    fn mark(&mut self, patterns: &[Pattern], row: usize, col: usize, num: u32) -> Option<Line> {
+        let (size, last) = (self.size, self.size - 1);
+        if std::mem::replace(&mut self.marked[row * size + col], true) {
+            return None;
+        }
+        self.rows[row] += 1;
+        self.cols[col] += 1;
+        self.diagonal += (row == col) as usize;
+        self.anti_diagonal += (row + col == last) as usize;
+        self.corners += self.is_corner(row, col) as usize;
+        self.total += 1;
+        self.unmarked_sum -= num as u64;
+        // a 1x1 board has a single corner
+        let corners = if size == 1 { 1 } else { 4 };
+        patterns.iter().find_map(|pattern| match pattern {
+            Pattern::Row => (self.rows[row] == size).then_some(Line::Row(row)),
+            Pattern::Column => (self.cols[col] == size).then_some(Line::Column(col)),
+            Pattern::Diagonal if row == col && self.diagonal == size => Some(Line::Diagonal),
+            Pattern::Diagonal if row + col == last && self.anti_diagonal == size => {
+                Some(Line::AntiDiagonal)
+            }
+            Pattern::Diagonal => None,
+            Pattern::Corners => {
+                (self.is_corner(row, col) && self.corners == corners).then_some(Line::Corners)
+            }
+            Pattern::FullCard => (self.total == size * size).then_some(Line::FullCard),
+        })
+    }
+}
+
+/// Plays the whole draw once, until every board has completed one of `patterns` or the numbers
+/// run out.
+///
+/// Every number is looked up in an index of where it appears, and each board keeps counts of the
+/// cells marked in every row, column and pattern, so a draw only costs as much as the number's
+/// occurrences.
+pub fn play(input: &ParsedInput, patterns: &[Pattern]) -> anyhow::Result<Ranking> {
+    // (board, row, col), in the order a scan would find them
+    let mut index: HashMap<u32, Vec<(usize, usize, usize)>> = HashMap::new();
+    for (b, board) in input.boards.iter().enumerate() {
+        for (i, &(num, _)) in board.cells.iter().enumerate() {
+            index
+                .entry(num)
+                .or_default()
+                .push((b, i / board.size, i % board.size));
+        }
+    }
+    let mut tallies: Vec<_> = input.boards.iter().map(Tally::new).collect();
+    let mut won = vec![false; input.boards.len()];
+    let mut winners = Vec::new();
+    for (draw, &number) in input.draw.numbers.iter().enumerate() {
+        if winners.len() == input.boards.len() {
+            break;
+        }
+        for &(board, row, col) in index.get(&number).into_iter().flatten() {
+            if won[board] {
+                continue;
+            }
+            let tally = &mut tallies[board];
+            if let Some(line) = tally.mark(patterns, row, col, number) {
+                won[board] = true;
+                winners.push(Win {
+                    board,
+                    draw,
+                    number,
+                    score: tally
+                        .unmarked_sum
+                        .checked_mul(number as u64)
+                        .context("score overflowed")?,
+                    line,
+                });
+            }
+        }
+    }
+    let losers = (0..input.boards.len()).filter(|&b| !won[b]).collect();
+    Ok(Ranking { winners, losers })
+}
+
+pub fn first_winner_score(input: &ParsedInput) -> anyhow::Result<u64> {
+    first_winner_score_with(input, &ROWS_AND_COLUMNS)
+}
+pub fn last_winner_score(input: &ParsedInput) -> anyhow::Result<u64> {
+    last_winner_score_with(input, &ROWS_AND_COLUMNS)
+}
+/// The score of the first board to complete any of `patterns`.
+pub fn first_winner_score_with(input: &ParsedInput, patterns: &[Pattern]) -> anyhow::Result<u64> {
+    match play(input, patterns)?.nth(0) {
+        Some(win) => Ok(win.score),
+        None => bail!("No one won yet"),
+    }
+}
+/// The score of the last board to complete any of `patterns`, once they all have.
+pub fn last_winner_score_with(input: &ParsedInput, patterns: &[Pattern]) -> anyhow::Result<u64> {
+    let ranking = play(input, patterns)?;
+    match ranking.winners.last() {
+        Some(win) if ranking.losers.is_empty() => Ok(win.score),
+        _ => bail!("More boards left"),
+    }
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Parsed = ParsedInput;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        first_winner_score(parsed)
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        last_winner_score(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::ParseError;
+    use proptest::prelude::*;
+
+    fn board() -> impl Strategy<Value = Board> {
+        (1..7usize).prop_flat_map(|size| {
+            prop::collection::vec(any::<u32>(), size * size).prop_map(move |cells| Board {
+                size,
+                cells: cells.into_iter().map(|num| (num, false)).collect(),
+            })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(
+            numbers in prop::collection::vec(any::<u32>(), 1..30),
+            boards in prop::collection::vec(board(), 1..6),
+        ) {
+            let input = ParsedInput { draw: Draw { numbers }, boards };
+            prop_assert_eq!(parse(&input.to_string()).ok(), Some(input));
+        }
+    }
+
+    const INPUT: &str = r#"7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7
+"#;
+    #[test]
+    fn part1() -> anyhow::Result<()> {
+        let parsed = parse(INPUT)?;
+        assert_eq!(first_winner_score(&parsed).unwrap(), 4512);
+        Ok(())
+    }
+    #[test]
+    fn part2() -> anyhow::Result<()> {
+        let parsed = parse(INPUT)?;
+        assert_eq!(last_winner_score(&parsed).unwrap(), 1924);
+        Ok(())
+    }
+    #[test]
+    fn parse_error() {
+        let input = INPUT.replacen(" 8  2 23", " 8  2 x3", 1);
+        let err = parse(&input).unwrap_err();
+        let err = err.downcast_ref::<ParseError>().expect("located error");
+        assert_eq!((err.line, err.column), (4, 7));
+        assert_eq!(err.context, vec!["board", "board row"]);
+
+        let input = INPUT.replacen(" 8  2 23  4 24", " 8  2 23  4", 1);
+        let err = parse(&input).unwrap_err();
+        let err = err.downcast_ref::<ParseError>().expect("located error");
+        assert_eq!(
+            (err.line, err.expected.as_str()),
+            (4, "wrong number of cells")
+        );
+    }
+    #[test]
+    fn board_sizes() -> anyhow::Result<()> {
+        let input = "3,1,7,9,5,2\n\n1 2\n3 4\n\n 1  2  3\n 4  5  6\n 7  8  9\n\n7";
+        let parsed = parse(input)?;
+        let sizes: Vec<_> = parsed.boards.iter().map(Board::size).collect();
+        assert_eq!(sizes, [2, 3, 1]);
+        // 3 then 1 completes the 2x2 board's first column
+        assert_eq!(first_winner_score(&parsed)?, 6);
+        assert_eq!(last_winner_score(&parsed)?, 2 * (4 + 6 + 8));
+
+        let err = parse("1\n\n1 2\n3 4\n5 6").unwrap_err();
+        let err = err.downcast_ref::<ParseError>().expect("located error");
+        assert_eq!(
+            (err.line, err.expected.as_str()),
+            (5, "wrong number of cells")
+        );
+        let err = parse("1\n\n1 2 3\n3 4 5").unwrap_err();
+        let err = err.downcast_ref::<ParseError>().expect("located error");
+        assert_eq!(
+            (err.line, err.context.as_slice()),
+            (4, ["board"].as_slice())
+        );
+        Ok(())
+    }
+    #[test]
+    fn patterns() -> anyhow::Result<()> {
+        let parsed = parse("1,5,3,7,9,2,4,6,8\n\n1 2 3\n4 5 6\n7 8 9")?;
+        let score = |patterns: &[Pattern]| first_winner_score_with(&parsed, patterns);
+        assert_eq!(score(&ROWS_AND_COLUMNS)?, 2 * (4 + 6 + 8));
+        assert_eq!(score(&[Pattern::Diagonal])?, 7 * (2 + 4 + 6 + 8 + 9));
+        assert_eq!(score(&[Pattern::Corners])?, 9 * (2 + 4 + 6 + 8));
+        assert_eq!(score(&[Pattern::FullCard])?, 0);
+        assert_eq!(score(&[Pattern::Corners, Pattern::Diagonal])?, 203);
+        assert!(score(&[]).is_err());
+
+        let parsed = parse(INPUT)?;
+        assert_eq!(first_winner_score_with(&parsed, &ROWS_AND_COLUMNS)?, 4512);
+        assert_eq!(last_winner_score_with(&parsed, &[Pattern::FullCard])?, 0);
+        Ok(())
+    }
+    #[test]
+    fn ranking() -> anyhow::Result<()> {
+        let parsed = parse(INPUT)?;
+        let ranking = play(&parsed, &ROWS_AND_COLUMNS)?;
+        let order: Vec<_> = ranking
+            .winners
+            .iter()
+            .map(|win| (win.board, win.draw, win.number, win.score))
+            .collect();
+        assert_eq!(
+            order,
+            [(2, 11, 24, 4512), (0, 13, 16, 2192), (1, 14, 13, 1924)]
+        );
+        assert_eq!(ranking.winners[0].line, Line::Row(0));
+        assert_eq!(ranking.nth(2).map(|win| win.line), Some(Line::Column(2)));
+        assert!(ranking.losers.is_empty());
+
+        let parsed = parse("1,5,9,2\n\n1 2\n3 4\n\n5 6\n7 8\n\n9")?;
+        let ranking = play(&parsed, &[Pattern::Row, Pattern::Diagonal])?;
+        assert_eq!(ranking.winners.len(), 2);
+        assert_eq!(ranking.nth(0).map(|win| win.board), Some(2));
+        assert_eq!(ranking.nth(1).map(|win| win.line), Some(Line::Row(0)));
+        assert_eq!(ranking.losers, [1]);
+        assert!(last_winner_score(&parsed).is_err());
+        Ok(())
+    }
+    #[test]
+    fn indexed() -> anyhow::Result<()> {
+        let all = [
+            Pattern::Row,
+            Pattern::Column,
+            Pattern::Diagonal,
+            Pattern::Corners,
+            Pattern::FullCard,
+        ];
+        let bingo = crate::generate::generator(4).expect("day 4 generator");
+        for seed in 0..4 {
+            let parsed = parse(&bingo.generate(30, seed))?;
+            for patterns in [
+                &ROWS_AND_COLUMNS[..],
+                &all[2..3],
+                &all[3..4],
+                &all[4..],
+                &all,
+            ] {
+                assert_eq!(play(&parsed, patterns)?, play_scanning(&parsed, patterns)?);
+            }
+        }
+        // repeated numbers, on a board and in the draw
+        let parsed = parse("4,4,1,3,2\n\n1 4\n4 2\n\n4 3\n2 2\n\n7")?;
+        for patterns in [&ROWS_AND_COLUMNS[..], &all[2..3], &all[3..4], &all] {
+            assert_eq!(play(&parsed, patterns)?, play_scanning(&parsed, patterns)?);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,727 @@
+use crate::{
+    parsing::{line, parse_all},
+    Solution,
+};
+use anyhow::bail;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, i32, line_ending},
+    combinator::{cut, map},
+    error::context,
+    multi::separated_list1,
+    sequence::separated_pair,
+    Parser,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::{self, Display},
+    io::{self, Write},
+};
+
+/// Coordinates are limited because [`lines_overlaped`] counts the overlaps on a dense diagram;
+/// [`lines_overlaped_sweep`] takes any.
+pub const MAX_COORD: i32 = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line {
+    a: (i32, i32),
+    b: (i32, i32),
+}
+impl Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Line { a, b } = self;
+        write!(f, "{},{} -> {},{}", a.0, a.1, b.0, b.1)
+    }
+}
+impl Line {
+    fn draw(&self, diagram: &mut [Vec<i32>]) {
+        for point in self.points() {
+            diagram[point.1 as usize][point.0 as usize] += 1;
+        }
+    }
+    pub fn hor_vert(&self) -> bool {
+        let Line { a, b } = self;
+        a.0 == b.0 || a.1 == b.1
+    }
+    pub fn diagonal(&self) -> bool {
+        let Line { a, b } = self;
+        a.0 == b.0 || a.1 == b.1 || a.0.abs_diff(b.0) == a.1.abs_diff(b.1)
+    }
+    /// The lattice points Bresenham's algorithm picks between the ends, ends included; exactly the
+    /// points on the line when it is horizontal, vertical or diagonal.
+    ///
+    /// Bresenham rounds halfway points towards where it started, so it always starts from the
+    /// smaller end: a line gives the same points whichever way round it is written.
+    pub fn points(&self) -> LineIter {
+        let (a, b) = (self.a.min(self.b), self.a.max(self.b));
+        LineIter {
+            cur: (a.0 as i64, a.1 as i64),
+            end: (b.0 as i64, b.1 as i64),
+            dx: (b.0 as i64 - a.0 as i64).abs(),
+            dy: -(b.1 as i64 - a.1 as i64).abs(),
+            step: (
+                (b.0 as i64 - a.0 as i64).signum(),
+                (b.1 as i64 - a.1 as i64).signum(),
+            ),
+            err: (b.0 as i64 - a.0 as i64).abs() - (b.1 as i64 - a.1 as i64).abs(),
+            done: false,
+        }
+    }
+}
+pub struct LineIter {
+    cur: (i64, i64),
+    end: (i64, i64),
+    dx: i64,
+    /// Negated, as Bresenham's error term needs it
+    dy: i64,
+    step: (i64, i64),
+    err: i64,
+    done: bool,
+}
+impl Iterator for LineIter {
+    type Item = (i32, i32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let cur = self.cur;
+        if cur == self.end {
+            self.done = true;
+        } else {
+            let e2 = 2 * self.err;
+            if e2 >= self.dy {
+                self.err += self.dy;
+                self.cur.0 += self.step.0;
+            }
+            if e2 <= self.dx {
+                self.err += self.dx;
+                self.cur.1 += self.step.1;
+            }
+        }
+        // both coordinates stay between the ends, which are i32s
+        Some((cur.0 as i32, cur.1 as i32))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ParsedInput {
+    lines: Vec<Line>,
+}
+
+pub fn parse(input: &str) -> anyhow::Result<ParsedInput> {
+    let coord = |i| context("coordinate", separated_pair(i32, cut(char(',')), cut(i32)))(i);
+    let vent = separated_pair(coord, cut(tag(" -> ")), cut(coord)).map(|(a, b)| Line { a, b });
+    let lines = separated_list1(line_ending, line("vent line", vent));
+    let parser = map(lines, |lines| ParsedInput { lines });
+    Ok(parse_all(input, parser)?)
+}
+pub fn lines_overlaped<P>(input: &ParsedInput, p: P) -> anyhow::Result<i32>
+where
+    P: FnMut(&Line) -> bool,
+{
+    if let Some(line) = input.lines.iter().find(|Line { a, b }| {
+        [a.0, a.1, b.0, b.1]
+            .iter()
+            .any(|c| !(0..MAX_COORD).contains(c))
+    }) {
+        bail!("vent line {} is outside the 0..{} diagram", line, MAX_COORD);
+    }
+    let lines: Vec<Line> = input.lines.iter().copied().filter(p).collect();
+    let diagram_size = lines
+        .iter()
+        .flat_map(|Line { a, b }| [a.0, b.0, a.1, b.1])
+        .max()
+        .map_or(0, |max| max as usize + 1);
+    let mut diagram = vec![vec![0i32; diagram_size]; diagram_size];
+    for line in &lines {
+        line.draw(&mut diagram);
+    }
+    Ok(diagram
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|&e| e >= 2)
+        .count() as i32)
+}
+
+/// How far right each kind of track moves per row: down-left diagonals, vertical lines and
+/// down-right diagonals.
+const SLOPES: [i64; 3] = [-1, 0, 1];
+
+/// A track: an index into [`SLOPES`], and the column where it crosses row 0.
+type TrackId = (usize, i64);
+
+/// The lines along one track that cross the current row.
+#[derive(Debug, Clone, Copy)]
+struct Track {
+    lines: u32,
+    /// The last row any of them reaches
+    until: i64,
+    /// When, in half rows, the track meets the next track to its right, if it does
+    meets: Option<(i64, TrackId)>,
+}
+
+/// What the sweep stops for, queued by the half row it happens on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Event {
+    /// The line with this index along the track ends, just after its last row
+    End(TrackId, usize),
+    /// Two neighbouring tracks meet, on a row or halfway between two when both are diagonal
+    Meet(TrackId, TrackId),
+}
+
+/// The tracks crossing the current row, ordered by column; tracks on the same column are ordered
+/// by slope, as they are just after it.
+///
+/// Tracks with the same slope never pass each other, so each slope keeps its tracks by where they
+/// cross row 0, and a track's neighbours are the nearest of the three slopes' nearest.
+#[derive(Debug, Default)]
+struct Sweep {
+    tracks: [BTreeMap<i64, Track>; 3],
+    /// Tracks holding at least two lines, each of which covers one cell twice on every row
+    stacked: u64,
+    events: BTreeSet<(i64, Event)>,
+}
+impl Sweep {
+    /// Twice the column of `track` at half row `t`.
+    fn column(track: TrackId, t: i64) -> i64 {
+        2 * track.1 + SLOPES[track.0] * t
+    }
+    /// The track with slope `slope` at twice the column `column` at half row `t`.
+    fn at(&self, slope: usize, column: i64, t: i64) -> Option<TrackId> {
+        let twice = column - SLOPES[slope] * t;
+        (twice % 2 == 0 && self.tracks[slope].contains_key(&(twice / 2)))
+            .then_some((slope, twice / 2))
+    }
+    /// The nearest track to the left or right of a track with slope `slope` at twice the column
+    /// `column`, just after half row `t`.
+    fn neighbour(&self, slope: usize, column: i64, t: i64, right: bool) -> Option<TrackId> {
+        let nearest = (0..3).filter_map(|other| {
+            let twice = column - SLOPES[other] * t;
+            let tracks = &self.tracks[other];
+            let found = if right {
+                let from = if other > slope {
+                    (twice + 1).div_euclid(2)
+                } else {
+                    twice.div_euclid(2) + 1
+                };
+                tracks.range(from..).next()
+            } else {
+                let to = if other < slope {
+                    twice.div_euclid(2)
+                } else {
+                    (twice + 1).div_euclid(2) - 1
+                };
+                tracks.range(..=to).next_back()
+            };
+            found.map(|(&start, _)| (other, start))
+        });
+        let key = |&track: &TrackId| (Self::column(track, t), track.0);
+        if right {
+            nearest.min_by_key(key)
+        } else {
+            nearest.max_by_key(key)
+        }
+    }
+    /// The number of tracks holding at least two lines through cell `x` on row `y`.
+    fn stacked_at(&self, x: i64, y: i64) -> u64 {
+        (0..3)
+            .filter(|&slope| {
+                self.tracks[slope]
+                    .get(&(x - SLOPES[slope] * y))
+                    .is_some_and(|track| track.lines >= 2)
+            })
+            .count() as u64
+    }
+    /// Makes `right` the track `left` meets next, as of half row `t`, queueing when they meet.
+    fn schedule(&mut self, left: TrackId, right: Option<TrackId>, t: i64) {
+        let meets = right.and_then(|right| {
+            let closing = SLOPES[left.0] - SLOPES[right.0];
+            if closing <= 0 {
+                return None;
+            }
+            let at = t + (Self::column(right, t) - Self::column(left, t)) / closing;
+            let until = self.tracks[left.0][&left.1].until;
+            let until = until.min(self.tracks[right.0][&right.1].until);
+            (at <= 2 * until).then_some((at, right))
+        });
+        let track = self.tracks[left.0]
+            .get_mut(&left.1)
+            .expect("track to schedule");
+        if let Some((at, right)) = std::mem::replace(&mut track.meets, meets) {
+            self.events.remove(&(at, Event::Meet(left, right)));
+        }
+        if let Some((at, right)) = meets {
+            self.events.insert((at, Event::Meet(left, right)));
+        }
+    }
+    /// Queues when every track at twice the column `column` meets its new neighbours, after
+    /// something happened there at half row `t`.
+    fn reschedule(&mut self, column: i64, t: i64) {
+        for slope in 0..3 {
+            let Some(track) = self.at(slope, column, t) else {
+                continue;
+            };
+            if let Some(left) = self.neighbour(slope, column, t, false) {
+                self.schedule(left, Some(track), t);
+            }
+            let right = self.neighbour(slope, column, t, true);
+            self.schedule(track, right, t);
+        }
+    }
+    fn start(&mut self, track: TrackId, until: i64) {
+        let entry = self.tracks[track.0].entry(track.1).or_insert(Track {
+            lines: 0,
+            until,
+            meets: None,
+        });
+        entry.lines += 1;
+        entry.until = entry.until.max(until);
+        if entry.lines == 2 {
+            self.stacked += 1;
+        }
+    }
+    /// Takes a line off `track` just after half row `t`, which is on a row.
+    fn end(&mut self, track: TrackId, t: i64) {
+        let entry = self.tracks[track.0]
+            .get_mut(&track.1)
+            .expect("track to end");
+        entry.lines -= 1;
+        match entry.lines {
+            0 => {
+                if let Some((at, right)) = entry.meets {
+                    self.events.remove(&(at, Event::Meet(track, right)));
+                }
+                self.tracks[track.0].remove(&track.1);
+                let column = Self::column(track, t);
+                if let Some(left) = self.neighbour(track.0, column, t, false) {
+                    let right = self.neighbour(track.0, column, t, true);
+                    self.schedule(left, right, t);
+                }
+            }
+            1 => self.stacked -= 1,
+            _ => {}
+        }
+    }
+    /// Takes the next event if it happens on half row `t`.
+    fn due(&mut self, t: i64) -> Option<Event> {
+        let &(at, event) = self.events.first()?;
+        (at == t).then(|| {
+            self.events.pop_first();
+            event
+        })
+    }
+}
+
+/// Counts the points where at least two of the lines `p` selects overlap, on any `i32`
+/// coordinates.
+///
+/// Sweeps the rows top to bottom in the manner of Bentley–Ottmann, stopping only where a line
+/// starts or ends and where two neighbouring lines meet; every row in between overlaps the same
+/// amount. Lines crossing more than one row follow one of three slopes, so their neighbours are
+/// found in an ordered map per slope, and only one meeting per line is queued at a time: memory
+/// grows with the number of lines, time with the lines and the points where they cross.
+/// Horizontal lines are taken a run at a time on their row rather than point by point.
+pub fn lines_overlaped_sweep<P>(input: &ParsedInput, p: P) -> anyhow::Result<u64>
+where
+    P: FnMut(&Line) -> bool,
+{
+    // runs on one row as (y, from, to), and the lines along a track as (top, bottom, track)
+    let mut runs: Vec<(i64, i64, i64)> = Vec::new();
+    let mut lines: Vec<(i64, i64, TrackId)> = Vec::new();
+    for line in input.lines.iter().copied().filter(p) {
+        let (a, b) = if line.a.1 <= line.b.1 {
+            (line.a, line.b)
+        } else {
+            (line.b, line.a)
+        };
+        let (ax, ay, bx, by) = (a.0 as i64, a.1 as i64, b.0 as i64, b.1 as i64);
+        if ay == by {
+            runs.push((ay, ax.min(bx), ax.max(bx)));
+        } else if ax == bx || (bx - ax).abs() == by - ay {
+            let dx = (bx - ax).signum();
+            lines.push((ay, by, ((dx + 1) as usize, ax - dx * ay)));
+        } else {
+            bail!(
+                "vent line {} is neither horizontal, vertical nor diagonal; use `overlaps` for any slope",
+                line
+            );
+        }
+    }
+    runs.sort_unstable();
+    lines.sort_unstable();
+    let (mut next_run, mut next_line) = (0, 0);
+    let mut sweep = Sweep::default();
+    let mut edges: Vec<(i64, i32)> = Vec::new();
+    let mut spans: Vec<(i64, i64)> = Vec::new();
+    let mut meetings: Vec<i64> = Vec::new();
+    let mut points: Vec<i64> = Vec::new();
+    let mut overlaps = 0u64;
+    // the first row not counted yet
+    let mut counted = i64::MIN;
+    // in half rows: rows are even, lines end and diagonals cross between them
+    while let Some(t) = [
+        runs.get(next_run).map(|run| 2 * run.0),
+        lines.get(next_line).map(|line| 2 * line.0),
+        sweep.events.first().map(|event| event.0),
+    ]
+    .into_iter()
+    .flatten()
+    .min()
+    {
+        // the rows before this event look like the last one
+        let y = (t + 1).div_euclid(2);
+        if sweep.stacked > 0 {
+            overlaps += sweep.stacked * (y - counted) as u64;
+        }
+        counted = y;
+        // every meeting now is taken before any track moves on, as moving one on can drop the
+        // meeting queued for its neighbour elsewhere on the same row
+        meetings.clear();
+        while let Some(event) = sweep.due(t) {
+            match event {
+                Event::End(track, _) => sweep.end(track, t - 1),
+                Event::Meet(left, _) => meetings.push(Sweep::column(left, t)),
+            }
+        }
+        for &column in &meetings {
+            sweep.reschedule(column, t);
+        }
+        if t != 2 * y {
+            continue;
+        }
+
+        // cells on this row where a line along a track meets any other line
+        points.clear();
+        points.extend(meetings.iter().map(|column| column / 2));
+        let starting = lines[next_line..]
+            .iter()
+            .take_while(|line| line.0 == y)
+            .count();
+        for (i, &(_, bottom, track)) in lines[next_line..][..starting].iter().enumerate() {
+            sweep.start(track, bottom);
+            sweep
+                .events
+                .insert((2 * bottom + 1, Event::End(track, next_line + i)));
+        }
+        for &(_, _, track) in &lines[next_line..][..starting] {
+            let column = Sweep::column(track, t);
+            if (0..3)
+                .filter(|&slope| sweep.at(slope, column, t).is_some())
+                .count()
+                > 1
+            {
+                points.push(column / 2);
+            }
+            sweep.reschedule(column, t);
+        }
+        next_line += starting;
+
+        // the runs cover cells once or twice themselves, and every track inside them
+        edges.clear();
+        spans.clear();
+        for &(_, from, to) in runs[next_run..].iter().take_while(|run| run.0 == y) {
+            edges.push((from, 1));
+            edges.push((to + 1, -1));
+            next_run += 1;
+        }
+        edges.sort_unstable();
+        let mut depth = 0;
+        for (&(x, change), &(next, _)) in edges.iter().zip(edges.iter().skip(1)) {
+            depth += change;
+            if depth >= 2 && x < next {
+                spans.push((x, next - 1));
+            }
+            if depth >= 1 && x < next {
+                for (slope, tracks) in sweep.tracks.iter().enumerate() {
+                    let shift = SLOPES[slope] * y;
+                    points.extend(
+                        tracks
+                            .range(x - shift..next - shift)
+                            .map(|(start, _)| start + shift),
+                    );
+                }
+            }
+        }
+        points.sort_unstable();
+        points.dedup();
+        // `sweep.stacked` counts a cell for each stacked track already, even where two meet
+        let stacked: u64 = points.iter().map(|&x| sweep.stacked_at(x, y)).sum();
+        spans.extend(points.iter().map(|&x| (x, x)));
+        spans.sort_unstable();
+        let mut covered = 0u64;
+        let mut reached = i64::MIN;
+        for &(from, to) in &spans {
+            let from = from.max(reached);
+            if from <= to {
+                covered += (to - from + 1) as u64;
+                reached = to + 1;
+            }
+        }
+        overlaps += sweep.stacked + covered - stacked;
+        counted = y + 1;
+    }
+    Ok(overlaps)
+}
+
+/// Every point at least two of the lines `p` selects cover, and how many cover it, in row
+/// order. Lines of any slope are rasterised with [`Line::points`].
+pub fn overlaps<P>(input: &ParsedInput, p: P) -> Vec<((i32, i32), usize)>
+where
+    P: FnMut(&Line) -> bool,
+{
+    let mut covered: HashMap<(i32, i32), usize> = HashMap::new();
+    for line in input.lines.iter().copied().filter(p) {
+        for point in line.points() {
+            *covered.entry(point).or_default() += 1;
+        }
+    }
+    let mut overlaps: Vec<_> = covered.into_iter().filter(|&(_, n)| n >= 2).collect();
+    overlaps.sort_unstable_by_key(|&((x, y), _)| (y, x));
+    overlaps
+}
+
+/// Writes `overlaps` as CSV with a header line.
+pub fn write_csv(out: &mut impl Write, overlaps: &[((i32, i32), usize)]) -> io::Result<()> {
+    writeln!(out, "x,y,lines")?;
+    for ((x, y), lines) in overlaps {
+        writeln!(out, "{},{},{}", x, y, lines)?;
+    }
+    Ok(())
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Parsed = ParsedInput;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        lines_overlaped_sweep(parsed, Line::hor_vert)
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        lines_overlaped_sweep(parsed, Line::diagonal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+    use proptest::prelude::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+    use std::{
+        collections::HashSet,
+        time::{Duration, Instant},
+    };
+
+    proptest! {
+        #[test]
+        fn round_trip(ends in prop::collection::vec(any::<[i32; 4]>(), 1..50)) {
+            let lines = ends
+                .into_iter()
+                .map(|[ax, ay, bx, by]| Line { a: (ax, ay), b: (bx, by) })
+                .collect_vec();
+            let text = lines.iter().join("\n");
+            prop_assert_eq!(parse(&text).ok().map(|p| p.lines), Some(lines));
+        }
+        #[test]
+        fn sweep_matches_diagram(ends in prop::collection::vec([0..20i32, 0..20, 0..20, 0..20], 1..30)) {
+            let lines = ends
+                .into_iter()
+                .map(|[ax, ay, bx, by]| Line { a: (ax, ay), b: (bx, by) })
+                .collect_vec();
+            let input = ParsedInput { lines };
+            for p in [Line::hor_vert, Line::diagonal] {
+                let dense = lines_overlaped(&input, p).unwrap() as u64;
+                prop_assert_eq!(lines_overlaped_sweep(&input, p).unwrap(), dense);
+            }
+        }
+        #[test]
+        fn sweep_skips_rows(ends in prop::collection::vec((0..400i32, 300..700i32, 0..4usize, 0..300i32), 2..8)) {
+            // few lines over many rows, so the sweep jumps between the rows where they meet
+            let lines = ends
+                .into_iter()
+                .map(|(x, y, kind, len)| {
+                    let (dx, dy) = [(1, 0), (0, 1), (1, 1), (1, -1)][kind];
+                    Line { a: (x, y), b: (x + dx * len, y + dy * len) }
+                })
+                .collect_vec();
+            let input = ParsedInput { lines };
+            let dense = lines_overlaped(&input, Line::diagonal).unwrap() as u64;
+            prop_assert_eq!(lines_overlaped_sweep(&input, Line::diagonal).unwrap(), dense);
+        }
+    }
+
+    const INPUT: &str = r#"0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2
+"#;
+    #[test]
+    fn part1() -> anyhow::Result<()> {
+        let parsed = parse(INPUT)?;
+        assert_eq!(lines_overlaped(&parsed, Line::hor_vert)?, 5);
+        Ok(())
+    }
+    #[test]
+    fn part2() -> anyhow::Result<()> {
+        let parsed = parse(INPUT)?;
+        assert_eq!(lines_overlaped(&parsed, Line::diagonal)?, 12);
+        Ok(())
+    }
+    #[test]
+    fn huge_coordinates() -> anyhow::Result<()> {
+        let parsed = parse(INPUT)?;
+        assert_eq!(lines_overlaped_sweep(&parsed, Line::hor_vert)?, 5);
+        assert_eq!(lines_overlaped_sweep(&parsed, Line::diagonal)?, 12);
+
+        let parsed = parse(
+            "-1000000,5 -> 1000000,5\n0,5 -> 2000000,5\n7,-2147483648 -> 7,2147483647\n\
+             2147483647,2147483647 -> 2147483640,2147483640\n2147483647,2147483640 -> 2147483640,2147483647",
+        )?;
+        assert!(lines_overlaped(&parsed, Line::diagonal).is_err());
+        // the 1000001 cells shared on row 5; the vertical line crosses them and the diagonals cross
+        // between cells
+        assert_eq!(lines_overlaped_sweep(&parsed, Line::hor_vert)?, 1_000_001);
+        assert_eq!(lines_overlaped_sweep(&parsed, Line::diagonal)?, 1_000_001);
+        let parsed = parse(
+            "0,-2000000000 -> 0,2000000000\n5,-2000000000 -> 5,2000000000\n\
+             0,2000000000 -> 0,2100000000\n-1000000000,-1000000000 -> 1000000000,1000000000\n\
+             10,-1000000000 -> 10,1000000000\n-5,-5 -> 1999999995,1999999995",
+        )?;
+        // the two vertical lines at 0 share a cell, and the diagonals share the 1000000006 cells
+        // from (-5, -5) to (1000000000, 1000000000), crossing the other vertical lines there
+        assert_eq!(lines_overlaped_sweep(&parsed, Line::hor_vert)?, 1);
+        assert_eq!(
+            lines_overlaped_sweep(&parsed, Line::diagonal)?,
+            1 + 1_000_000_006
+        );
+        let parsed = parse("0,0 -> 2,1")?;
+        assert_eq!(lines_overlaped_sweep(&parsed, Line::diagonal)?, 0);
+        assert!(lines_overlaped_sweep(&parsed, |_| true).is_err());
+        // neither diagonal nor straight, however far apart the ends
+        let parsed = parse("-2147483648,0 -> 2147483647,5\n-2147483648,0 -> 2147483647,5")?;
+        assert_eq!(lines_overlaped_sweep(&parsed, Line::hor_vert)?, 0);
+        assert_eq!(lines_overlaped_sweep(&parsed, Line::diagonal)?, 0);
+        let parsed = parse("-2147483648,-2147483648 -> 2147483647,2147483647\n0,0 -> 0,1")?;
+        assert_eq!(lines_overlaped_sweep(&parsed, Line::diagonal)?, 1);
+        Ok(())
+    }
+    #[test]
+    fn large_input() {
+        // hundreds of diagonals each crossing all the others, which share a cell where they cross
+        // on a row; every other pair crosses between two rows
+        let mut lines = (0..300)
+            .flat_map(|i| {
+                let (top, bottom) = (-1_000_000_000, 1_000_000_000 - 4000);
+                [
+                    Line {
+                        a: (top + 2 * i, top),
+                        b: (bottom + 2 * i, bottom),
+                    },
+                    Line {
+                        a: (-top - i, top),
+                        b: (-bottom - i, bottom),
+                    },
+                ]
+            })
+            .collect_vec();
+        let start = Instant::now();
+        let crossing = ParsedInput {
+            lines: lines.clone(),
+        };
+        assert_eq!(
+            lines_overlaped_sweep(&crossing, Line::diagonal).unwrap(),
+            150 * 300
+        );
+        // and thousands of long lines at random, however far apart the rows they overlap on
+        let mut rng = ChaCha8Rng::seed_from_u64(5);
+        lines.extend((0..2000).map(|_| {
+            let (x, y) = (
+                rng.gen_range(-1_000_000..1_000_000),
+                rng.gen_range(-1_000_000..1_000_000),
+            );
+            let (dx, dy) = [(1, 0), (0, 1), (1, 1), (1, -1)][rng.gen_range(0..4)];
+            let len = rng.gen_range(0..1_000_000);
+            Line {
+                a: (x, y),
+                b: (x + dx * len, y + dy * len),
+            }
+        }));
+        let input = ParsedInput { lines };
+        for p in [Line::hor_vert, Line::diagonal] {
+            lines_overlaped_sweep(&input, p).unwrap();
+        }
+        // looking at every row where two lines might meet takes minutes here
+        assert!(
+            start.elapsed() < Duration::from_secs(30),
+            "took {:?}",
+            start.elapsed()
+        );
+    }
+    #[test]
+    fn any_slope() {
+        let line = |a, b| Line { a, b };
+        let points = |a, b| line(a, b).points().collect_vec();
+        assert_eq!(points((0, 0), (2, 1)), [(0, 0), (1, 1), (2, 1)]);
+        assert_eq!(points((1, 1), (1, 1)), [(1, 1)]);
+        assert_eq!(
+            points((0, 0), (-6, 2)),
+            [(-6, 2), (-5, 2), (-4, 1), (-3, 1), (-2, 1), (-1, 0), (0, 0)]
+        );
+        let steep = points((3, -4), (0, 5));
+        assert_eq!(steep.len(), 10);
+        assert!(steep.iter().map(|p| p.1).sorted().eq(-4..=5));
+        for (a, b) in [
+            ((0, 0), (4, 2)),
+            ((3, -4), (0, 5)),
+            ((-7, 1), (2, 8)),
+            ((5, 5), (-5, 4)),
+        ] {
+            let forward: HashSet<_> = points(a, b).into_iter().collect();
+            let backward: HashSet<_> = points(b, a).into_iter().collect();
+            assert_eq!(forward, backward);
+        }
+        let far = points((i32::MIN, i32::MAX), (i32::MIN + 3, i32::MAX - 1));
+        assert_eq!(far.first(), Some(&(i32::MIN, i32::MAX)));
+        assert_eq!(far.last(), Some(&(i32::MIN + 3, i32::MAX - 1)));
+        // the same points the unit stepping gave for the puzzle's lines
+        let diagonal = line((8, 0), (0, 8)).points().collect_vec();
+        assert_eq!(diagonal.len(), 9);
+        assert!(diagonal.iter().all(|&(x, y)| x + y == 8));
+    }
+    #[test]
+    fn hotspots() -> anyhow::Result<()> {
+        let parsed = parse(INPUT)?;
+        let spots = overlaps(&parsed, Line::diagonal);
+        assert_eq!(spots.len(), 12);
+        assert_eq!(spots.iter().filter(|&&(_, n)| n == 3).count(), 2);
+        assert_eq!(spots[0], ((7, 1), 2));
+        let mut out = Vec::new();
+        write_csv(&mut out, &overlaps(&parsed, Line::hor_vert)[..2])?;
+        assert_eq!(String::from_utf8_lossy(&out), "x,y,lines\n3,4,2\n7,4,2\n");
+
+        let parsed = parse("0,0 -> 4,2\n0,2 -> 4,0\n2,0 -> 2,4")?;
+        // at x = 1 the shallow lines are at y = 0.5 and 1.5, and both land on 1
+        assert_eq!(overlaps(&parsed, |_| true), [((1, 1), 2), ((2, 1), 3)]);
+        let parsed = parse("0,0 -> 4,2\n4,2 -> 0,0")?;
+        let spots = overlaps(&parsed, |_| true);
+        assert_eq!(spots.len(), 5);
+        assert!(spots.iter().all(|&(_, n)| n == 2));
+        Ok(())
+    }
+}
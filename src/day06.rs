@@ -0,0 +1,100 @@
+use crate::{
+    parsing::{line, parse_all},
+    Solution,
+};
+use anyhow::Context;
+use nom::{
+    character::complete::{char, u8},
+    combinator::{map, verify},
+    multi::separated_list1,
+};
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FishSim {
+    fishes: [u64; FishSim::FIRST_CYCLE + 1],
+}
+impl FishSim {
+    const FIRST_CYCLE: usize = 8;
+    const NORMAL_CYCLE: usize = 6;
+    fn new(initial: Vec<u8>) -> Self {
+        let mut fishes = [0; FishSim::FIRST_CYCLE + 1];
+
+        for fish in initial {
+            fishes[fish as usize] += 1;
+        }
+        Self { fishes }
+    }
+
+    pub fn fishes_after(&mut self, days: u32) -> anyhow::Result<u64> {
+        let overflow = || format!("more than {} fishes", u64::MAX);
+        for _ in 1..=days {
+            self.fishes.rotate_left(1);
+            self.fishes[FishSim::NORMAL_CYCLE] = self.fishes[FishSim::NORMAL_CYCLE]
+                .checked_add(self.fishes[FishSim::FIRST_CYCLE])
+                .with_context(overflow)?;
+        }
+        self.fishes
+            .into_iter()
+            .try_fold(0u64, |acc, n| acc.checked_add(n))
+            .with_context(overflow)
+    }
+}
+
+pub fn parse(input: &str) -> anyhow::Result<FishSim> {
+    let fishes = line(
+        "fish timers",
+        separated_list1(
+            char(','),
+            verify(u8, |&timer| timer as usize <= FishSim::FIRST_CYCLE),
+        ),
+    );
+    let parser = map(fishes, FishSim::new);
+    Ok(parse_all(input, parser)?)
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    type Parsed = FishSim;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        parsed.clone().fishes_after(80)
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        parsed.clone().fishes_after(256)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn round_trip(timers in prop::collection::vec(0..=FishSim::FIRST_CYCLE as u8, 1..100)) {
+            let text = timers.iter().join(",");
+            prop_assert_eq!(parse(&text).ok(), Some(FishSim::new(timers)));
+        }
+    }
+    const INPUT: &str = "3,4,3,1,2";
+    #[test]
+    fn part1() -> anyhow::Result<()> {
+        let mut sim = parse(INPUT)?;
+        assert_eq!(sim.fishes_after(18)?, 26);
+        assert_eq!(sim.fishes_after(80 - 18)?, 5934);
+        Ok(())
+    }
+    #[test]
+    fn part2() -> anyhow::Result<()> {
+        let mut sim = parse(INPUT)?;
+        assert_eq!(sim.fishes_after(256)?, 26984457539);
+        Ok(())
+    }
+}
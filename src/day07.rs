@@ -0,0 +1,86 @@
+use crate::{
+    parsing::{line, parse_all},
+    Solution,
+};
+use anyhow::Context;
+use nom::{
+    character::complete::{char, i32},
+    multi::separated_list1,
+};
+pub fn parse(input: &str) -> anyhow::Result<Vec<i32>> {
+    let positions = line("crab positions", separated_list1(char(','), i32));
+    Ok(parse_all(input, positions)?)
+}
+/// Fuel for every crab to reach `target`, each paying `cost` for its distance.
+fn fuel(offsets: &[i32], target: i64, cost: impl Fn(i64) -> Option<i64>) -> Option<i64> {
+    offsets.iter().try_fold(0i64, |acc, &o| {
+        acc.checked_add(cost((o as i64 - target).abs())?)
+    })
+}
+pub fn alignment_cost_constant(offsets: &[i32]) -> anyhow::Result<i64> {
+    let mut local = offsets.to_vec();
+    local.sort_unstable();
+    let median = *local.get(local.len() / 2).context("no crabs")?;
+    fuel(&local, median as i64, Some).context("fuel overflowed")
+}
+pub fn alignment_cost_progressive(offsets: &[i32]) -> anyhow::Result<i64> {
+    anyhow::ensure!(!offsets.is_empty(), "no crabs");
+    let sum = offsets.iter().map(|&o| o as i64).sum::<i64>();
+    let mean_floor = sum.div_euclid(offsets.len() as i64);
+    //Fold on arithmetic progression
+    let progressive = |d: i64| d.checked_mul(d + 1).map(|n| n / 2);
+    let fuel_for_mean = fuel(offsets, mean_floor, progressive);
+    let fuel_for_mean_ceil = fuel(offsets, mean_floor + 1, progressive);
+    fuel_for_mean
+        .into_iter()
+        .chain(fuel_for_mean_ceil)
+        .min()
+        .context("fuel overflowed")
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    type Parsed = Vec<i32>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        alignment_cost_constant(parsed)
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        alignment_cost_progressive(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn round_trip(positions in prop::collection::vec(any::<i32>(), 1..100)) {
+            let text = positions.iter().join(",");
+            prop_assert_eq!(parse(&text).ok(), Some(positions));
+        }
+    }
+    const INPUT: &str = "16,1,2,0,4,2,7,1,2,14";
+    #[test]
+    fn part1() -> anyhow::Result<()> {
+        let offsets = parse(INPUT)?;
+        assert_eq!(alignment_cost_constant(&offsets)?, 37);
+        Ok(())
+    }
+    #[test]
+    fn part2() -> anyhow::Result<()> {
+        let offsets = parse(INPUT)?;
+        assert_eq!(alignment_cost_progressive(&offsets)?, 168);
+        Ok(())
+    }
+}
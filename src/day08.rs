@@ -0,0 +1,184 @@
+use crate::{
+    parsing::{line, parse_all},
+    Solution,
+};
+use anyhow::{bail, Context};
+use nom::{
+    character::complete::{alpha1, char, line_ending, multispace1, space1},
+    combinator::map,
+    multi::separated_list1,
+    sequence::separated_pair,
+    Parser,
+};
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedInput {
+    mapping: HashMap<SegmentPatterns, DigitalOutput>,
+}
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct SegmentPatterns {
+    patterns: Vec<BTreeSet<char>>,
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigitalOutput {
+    digits: Vec<BTreeSet<char>>,
+}
+pub fn parse(input: &str) -> anyhow::Result<ParsedInput> {
+    let words = |i| {
+        separated_list1(
+            space1,
+            alpha1.map(|w: &str| w.chars().collect::<BTreeSet<_>>()),
+        )(i)
+    };
+    let entry = |i| {
+        separated_pair(
+            words.map(|patterns| SegmentPatterns { patterns }),
+            space1.and(char('|')).and(multispace1),
+            words.map(|output| DigitalOutput { digits: output }),
+        )(i)
+    };
+    let entries = separated_list1(line_ending, line("note entry", entry));
+    let parser = map(entries, |entries| ParsedInput {
+        mapping: entries.into_iter().collect(),
+    });
+    Ok(parse_all(input, parser)?)
+}
+pub fn count_digits(input: &ParsedInput) -> i32 {
+    input
+        .mapping
+        .values()
+        .flat_map(|outputs| &outputs.digits)
+        .filter(|o| [2, 4, 3, 7].contains(&o.len()))
+        .count() as i32
+}
+pub fn decode_digits(input: &ParsedInput) -> anyhow::Result<u64> {
+    input
+        .mapping
+        .iter()
+        .map(|(segment_pattern, digit_output)| {
+            let find = |len| {
+                segment_pattern
+                    .patterns
+                    .iter()
+                    .find(|pat| pat.len() == len)
+                    .with_context(|| format!("no signal pattern with {} segments", len))
+            };
+            let four = find(4)?;
+            let seven = find(3)?;
+            digit_output.digits.iter().try_fold(0u64, |acc, o| {
+                let deduct_rules = (
+                    o.len(),
+                    o.intersection(four).count(),
+                    o.intersection(seven).count(),
+                );
+                let digit = match deduct_rules {
+                    (2, _, _) => 1,
+                    (3, _, _) => 7,
+                    (4, _, _) => 4,
+                    (5, 2, 2) => 2,
+                    (5, 3, 3) => 3,
+                    (5, 3, 2) => 5,
+                    (6, 3, 3) => 0,
+                    (6, 3, 2) => 6,
+                    (6, 4, 3) => 9,
+                    (7, _, _) => 8,
+                    _ => bail!("can't decode output digit {}", o.iter().collect::<String>()),
+                };
+                acc.checked_mul(10)
+                    .and_then(|acc| acc.checked_add(digit))
+                    .context("output value overflowed")
+            })
+        })
+        .try_fold(0u64, |sum, value| {
+            sum.checked_add(value?).context("sum overflowed")
+        })
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    type Parsed = ParsedInput;
+    type Part1 = i32;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        Ok(count_digits(parsed))
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        decode_digits(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn words(len: std::ops::Range<usize>) -> impl Strategy<Value = Vec<BTreeSet<char>>> {
+        let segment = prop::sample::select(('a'..='g').collect::<Vec<_>>());
+        prop::collection::vec(prop::collection::btree_set(segment, 1..=7), len)
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(entries in prop::collection::vec((words(1..11), words(1..5)), 1..20)) {
+            let show = |words: &[BTreeSet<char>]| {
+                let words: Vec<String> = words.iter().map(|w| w.iter().collect()).collect();
+                words.join(" ")
+            };
+            let text: Vec<_> = entries
+                .iter()
+                .map(|(patterns, digits)| format!("{} | {}", show(patterns), show(digits)))
+                .collect();
+            let mapping = entries
+                .into_iter()
+                .map(|(patterns, digits)| (SegmentPatterns { patterns }, DigitalOutput { digits }))
+                .collect();
+            prop_assert_eq!(parse(&text.join("\n")).ok(), Some(ParsedInput { mapping }));
+        }
+    }
+
+    const INPUT: &str = r#"be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb |
+fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec |
+fcgedb cgb dgebacf gc
+fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef |
+cg cg fdcagb cbg
+fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega |
+efabcd cedba gadfec cb
+aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga |
+gecf egdcabf bgf bfgea
+fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf |
+gebdcfa ecba ca fadegcb
+dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf |
+cefg dcbef fcge gbcadfe
+bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd |
+ed bcgafe cdgba cbgef
+egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg |
+gbdfcae bgc cg cgb
+gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc |
+fgae cfgab fg bagce
+"#;
+    #[test]
+    fn part1() -> anyhow::Result<()> {
+        let parsed = parse(INPUT)?;
+        assert_eq!(count_digits(&parsed), 26);
+        Ok(())
+    }
+    #[test]
+    fn part2() -> anyhow::Result<()> {
+        let parsed = parse(INPUT)?;
+        assert_eq!(decode_digits(&parsed)?, 61229);
+
+        let err = decode_digits(&parse("ab abc abcd | abcdefg abcde")?).unwrap_err();
+        assert_eq!(err.to_string(), "can't decode output digit abcde");
+        let err = decode_digits(&parse("ab abc | ab")?).unwrap_err();
+        assert_eq!(err.to_string(), "no signal pattern with 4 segments");
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{
+    grid::{char_grid, Grid, Pos},
+    parsing::parse_all,
+    Solution,
+};
+use anyhow::Context;
+use nom::{character::complete::satisfy, Parser};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+#[derive(Debug, Clone)]
+pub struct ParsedInput {
+    pub height_map: Grid<u32>,
+}
+pub fn parse(input: &str) -> anyhow::Result<ParsedInput> {
+    let height = satisfy(|c| c.is_ascii_digit()).map(|c| c as u32 - '0' as u32);
+    let height_map = parse_all(input, char_grid("height row", height))?;
+    Ok(ParsedInput { height_map })
+}
+pub fn risk_level(height_map: &Grid<u32>, lowest_points: &[Pos]) -> u64 {
+    lowest_points
+        .iter()
+        .fold(0, |acc, pos| acc + height_map[*pos] as u64 + 1)
+}
+fn basin_fill(hm: &Grid<u32>, lowest_point: Pos) -> HashSet<Pos> {
+    let mut basin = HashSet::new();
+    let mut stack = vec![lowest_point];
+    while let Some(point) = stack.pop() {
+        if !basin.insert(point) {
+            continue;
+        }
+        stack.extend(
+            hm.neighbours4(point)
+                .filter(|adj| hm[*adj] != 9 && hm[*adj] > hm[point] && !basin.contains(adj)),
+        );
+    }
+    basin
+}
+pub fn basin_sizes_mul(hm: &Grid<u32>, lowest_points: &[Pos]) -> anyhow::Result<u64> {
+    let mut basins: Vec<_> = lowest_points.iter().map(|lp| basin_fill(hm, *lp)).collect();
+    basins.sort_unstable_by_key(|basin| Reverse(basin.len()));
+    basins
+        .iter()
+        .take(3)
+        .try_fold(1u64, |acc, v| acc.checked_mul(v.len() as u64))
+        .context("product of basin sizes overflowed")
+}
+pub fn lowest_points(input: &ParsedInput) -> Vec<Pos> {
+    let hm = &input.height_map;
+    hm.positions()
+        .filter(|&pos| hm.neighbours4(pos).all(|adj| hm[adj] > hm[pos]))
+        .collect()
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    type Parsed = ParsedInput;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        Ok(risk_level(&parsed.height_map, &lowest_points(parsed)))
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        basin_sizes_mul(&parsed.height_map, &lowest_points(parsed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const INPUT: &str = r#"2199943210
+3987894921
+9856789892
+8767896789
+9899965678
+"#;
+    #[test]
+    fn part1() -> anyhow::Result<()> {
+        let parsed = parse(INPUT)?;
+        let lowest_points = lowest_points(&parsed);
+        assert_eq!(risk_level(&parsed.height_map, &lowest_points), 15);
+        Ok(())
+    }
+    #[test]
+    fn part2() -> anyhow::Result<()> {
+        let parsed = parse(INPUT)?;
+        let lowest_points = lowest_points(&parsed);
+        assert_eq!(basin_sizes_mul(&parsed.height_map, &lowest_points)?, 1134);
+        Ok(())
+    }
+}
@@ -0,0 +1,258 @@
+use crate::{
+    parsing::{line, parse_all},
+    Solution,
+};
+use anyhow::Context;
+use nom::{
+    character::complete::{line_ending, one_of},
+    combinator::map,
+    multi::{many1, separated_list1},
+    Parser,
+};
+use std::{fmt, ops::Not};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bracket {
+    kind: BracketKind,
+    side: BracketSide,
+}
+impl Bracket {
+    fn score_corrupted(&self) -> u64 {
+        use self::{BracketKind::*, BracketSide::*};
+        match self {
+            Bracket {
+                kind: Paren,
+                side: Close,
+            } => 3,
+            Bracket {
+                kind: Square,
+                side: Close,
+            } => 57,
+            Bracket {
+                kind: Curly,
+                side: Close,
+            } => 1197,
+            Bracket {
+                kind: Angle,
+                side: Close,
+            } => 25137,
+            _ => 0,
+        }
+    }
+    fn score_incomplete(&self) -> u64 {
+        use self::{BracketKind::*, BracketSide::*};
+        match self {
+            Bracket {
+                kind: Paren,
+                side: Close,
+            } => 1,
+            Bracket {
+                kind: Square,
+                side: Close,
+            } => 2,
+            Bracket {
+                kind: Curly,
+                side: Close,
+            } => 3,
+            Bracket {
+                kind: Angle,
+                side: Close,
+            } => 4,
+            _ => 0,
+        }
+    }
+    fn is_balanced(&self, other: &Bracket) -> bool {
+        self.kind == other.kind
+            && self.side == BracketSide::Open
+            && other.side == BracketSide::Close
+    }
+}
+impl fmt::Display for Bracket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (open, close) = match self.kind {
+            BracketKind::Paren => ('(', ')'),
+            BracketKind::Square => ('[', ']'),
+            BracketKind::Curly => ('{', '}'),
+            BracketKind::Angle => ('<', '>'),
+        };
+        match self.side {
+            BracketSide::Open => write!(f, "{}", open),
+            BracketSide::Close => write!(f, "{}", close),
+        }
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BracketKind {
+    Paren,
+    Square,
+    Curly,
+    Angle,
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BracketSide {
+    Open,
+    Close,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedInput {
+    chunks: Vec<Vec<Bracket>>,
+}
+pub fn parse(input: &str) -> anyhow::Result<ParsedInput> {
+    use self::{BracketKind::*, BracketSide::*};
+    let kind = |c| match c {
+        '(' | ')' => Paren,
+        '[' | ']' => Square,
+        '{' | '}' => Curly,
+        _ => Angle,
+    };
+    let side = |c| if "([{<".contains(c) { Open } else { Close };
+    let bracket = one_of("([{<>}])").map(|c| Bracket {
+        kind: kind(c),
+        side: side(c),
+    });
+    let chunk = line("navigation line", many1(bracket));
+    let chunks = separated_list1(line_ending, chunk);
+    let parser = map(chunks, |chunks| ParsedInput { chunks });
+    Ok(parse_all(input, parser)?)
+}
+pub fn corrupted_chunks_score(input: &ParsedInput) -> u64 {
+    let mut queue = Vec::new();
+    input
+        .chunks
+        .iter()
+        .filter_map(|chunk| {
+            queue.clear();
+            for bracket in chunk {
+                match bracket {
+                    Bracket {
+                        side: BracketSide::Open,
+                        ..
+                    } => queue.push(bracket),
+                    Bracket {
+                        side: BracketSide::Close,
+                        ..
+                    } => {
+                        if !queue.pop().is_some_and(|open| open.is_balanced(bracket)) {
+                            return Some(bracket);
+                        }
+                    }
+                }
+            }
+            None
+        })
+        .fold(0, |acc, bracket| acc + bracket.score_corrupted())
+}
+
+pub fn incomplete_chunks_middle_score(input: &ParsedInput) -> anyhow::Result<u64> {
+    let mut incomplete_scores: Vec<u64> = input
+        .chunks
+        .iter()
+        .filter_map(|chunk| {
+            let mut queue = Vec::new();
+            for bracket in chunk {
+                match bracket {
+                    Bracket {
+                        side: BracketSide::Open,
+                        ..
+                    } => queue.push(bracket),
+                    Bracket {
+                        side: BracketSide::Close,
+                        ..
+                    } => {
+                        if !queue.pop().is_some_and(|open| open.is_balanced(bracket)) {
+                            return None;
+                        }
+                    }
+                }
+            }
+            queue.is_empty().not().then_some(queue)
+        })
+        .map(|incomplete| {
+            incomplete
+                .into_iter()
+                .rev()
+                .map(|bracket| Bracket {
+                    kind: bracket.kind,
+                    side: BracketSide::Close,
+                })
+                .try_fold(0u64, |acc, bracket| {
+                    acc.checked_mul(5)?.checked_add(bracket.score_incomplete())
+                })
+                .context("completion score overflowed")
+        })
+        .collect::<anyhow::Result<_>>()?;
+    incomplete_scores.sort_unstable();
+    incomplete_scores
+        .get(incomplete_scores.len() / 2)
+        .copied()
+        .context("no incomplete lines")
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Parsed = ParsedInput;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        Ok(corrupted_chunks_score(parsed))
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        incomplete_chunks_middle_score(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
+    fn bracket() -> impl Strategy<Value = Bracket> {
+        use self::{BracketKind::*, BracketSide::*};
+        let kind = prop::sample::select(vec![Paren, Square, Curly, Angle]);
+        let side = prop::sample::select(vec![Open, Close]);
+        (kind, side).prop_map(|(kind, side)| Bracket { kind, side })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(chunks in prop::collection::vec(prop::collection::vec(bracket(), 1..30), 1..20)) {
+            let text = chunks.iter().map(|chunk| chunk.iter().join("")).join("\n");
+            prop_assert_eq!(parse(&text).ok(), Some(ParsedInput { chunks }));
+        }
+    }
+
+    const INPUT: &str = r#"[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(
+{([(<{}[<>[]}>{[]{[(<()>
+(((({<>}<{<{<>}{[]{[]{}
+[[<[([]))<([[{}[[()]]]
+[{[{({}]{}}([{[{{{}}([]
+{<[[]]>}<{[{[{[]{()[[[]
+[<(<(<(<{}))><([]([]()
+<{([([[(<>()){}]>(<<{{
+<{([{{}}[<[[[<>{}]]]>[]]
+"#;
+    #[test]
+    fn part1() -> anyhow::Result<()> {
+        let parsed = parse(INPUT)?;
+        assert_eq!(corrupted_chunks_score(&parsed), 26397);
+        // a closing bracket with nothing open is corrupted too
+        assert_eq!(corrupted_chunks_score(&parse(">()")?), 25137);
+        Ok(())
+    }
+    #[test]
+    fn part2() -> anyhow::Result<()> {
+        let parsed = parse(INPUT)?;
+        assert_eq!(incomplete_chunks_middle_score(&parsed)?, 288957);
+        assert!(incomplete_chunks_middle_score(&parse(&"(".repeat(30))?).is_err());
+        Ok(())
+    }
+}
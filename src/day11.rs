@@ -0,0 +1,148 @@
+use crate::{
+    grid::{char_grid, Grid, Pos},
+    parsing::parse_all,
+    render::{Frame, Pixel, Render},
+    Solution,
+};
+use anyhow::bail;
+use nom::{character::complete::satisfy, combinator::map, Parser};
+
+/// `all_flash_step` gives up after this many steps.
+pub const MAX_STEPS: u32 = 100_000;
+
+#[derive(Debug, Clone)]
+pub struct OctoSim {
+    energy_map: Grid<u8>,
+    flash_counter: u64,
+}
+
+impl OctoSim {
+    fn new(energy_map: Grid<u8>) -> Self {
+        Self {
+            energy_map,
+            flash_counter: 0,
+        }
+    }
+    fn step(&mut self) {
+        let mut flashing: Vec<_> = self
+            .energy_map
+            .positions()
+            .filter(|pos| self.energy_up(*pos))
+            .collect();
+        while let Some(pos) = flashing.pop() {
+            self.flash_counter += 1;
+            let victims: Vec<_> = self.energy_map.neighbours8(pos).collect();
+            for victim in victims {
+                if self.energy_map[victim] != 0 && self.energy_up(victim) {
+                    flashing.push(victim);
+                }
+            }
+        }
+    }
+    /// Whether the octopus at `pos` flashes.
+    fn energy_up(&mut self, pos: Pos) -> bool {
+        self.energy_map[pos] = (self.energy_map[pos] + 1) % 10;
+        self.energy_map[pos] == 0
+    }
+    /// This state and the one after each step, up to the first step where every octopus
+    /// flashes at once.
+    pub fn states(self) -> impl Iterator<Item = OctoSim> {
+        let mut synced = false;
+        std::iter::successors(Some(self), |sim| {
+            let mut sim = sim.clone();
+            sim.step();
+            Some(sim)
+        })
+        .take_while(move |sim| {
+            let more = !synced;
+            synced = sim.flash_counter > 0 && sim.energy_map.iter().all(|e| *e == 0);
+            more
+        })
+    }
+}
+impl Render for OctoSim {
+    /// Flashing octopuses are bright, the others get lighter as their energy builds up.
+    fn render(&self) -> Frame {
+        self.energy_map.map(|&energy| Pixel {
+            glyph: (b'0' + energy) as char,
+            colour: match energy {
+                0 => [255, 250, 200],
+                e => [e * 6, e * 12, 40 + e * 18],
+            },
+        })
+    }
+}
+
+pub fn parse(input: &str) -> anyhow::Result<OctoSim> {
+    let energy = satisfy(|c| c.is_ascii_digit()).map(|c| c as u8 - b'0');
+    let parser = map(char_grid("energy level row", energy), OctoSim::new);
+    Ok(parse_all(input, parser)?)
+}
+pub fn octo_flashes_count(sim: &mut OctoSim) -> u64 {
+    for _ in 0..100 {
+        sim.step();
+    }
+    sim.flash_counter
+}
+pub fn all_flash_step(sim: &mut OctoSim) -> anyhow::Result<u32> {
+    for step in 1..=MAX_STEPS {
+        sim.step();
+        if sim.energy_map.iter().all(|e| *e == 0) {
+            return Ok(step);
+        }
+    }
+    bail!(
+        "the octopuses don't all flash at once in {} steps",
+        MAX_STEPS
+    )
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Parsed = OctoSim;
+    type Part1 = u64;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        Ok(octo_flashes_count(&mut parsed.clone()))
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        all_flash_step(&mut parsed.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526"#;
+    #[test]
+    fn part1() -> anyhow::Result<()> {
+        let mut parsed = parse(INPUT)?;
+        assert_eq!(octo_flashes_count(&mut parsed), 1656);
+        Ok(())
+    }
+    #[test]
+    fn part2() -> anyhow::Result<()> {
+        let parsed = parse(INPUT)?;
+        assert_eq!(all_flash_step(&mut parsed.clone())?, 195);
+        let frames = parsed.states().map(|sim| sim.render()).collect::<Vec<_>>();
+        assert_eq!(frames.len(), 196);
+        assert!(frames[195].iter().all(|p| p.glyph == '0'));
+        Ok(())
+    }
+}
@@ -0,0 +1,230 @@
+use crate::{
+    parsing::{line, parse_all},
+    Solution,
+};
+use anyhow::{bail, Context};
+use itertools::Itertools;
+use nom::{
+    character::complete::{alpha1, char, line_ending},
+    combinator::{cut, map},
+    multi::separated_list1,
+    sequence::separated_pair,
+};
+use petgraph::{
+    graph::{NodeIndex, UnGraph},
+    visit::EdgeRef,
+    Graph,
+};
+use std::{collections::HashMap, fmt};
+
+#[derive(Debug, Clone)]
+pub struct Caves {
+    graph: UnGraph<String, ()>,
+    nodes_map: HashMap<String, NodeIndex>,
+}
+
+impl Caves {
+    fn new(edges: Vec<(&str, &str)>) -> Self {
+        let mut graph = Graph::new_undirected();
+        let mut nodes_map = HashMap::new();
+        for (a, b) in edges {
+            let mut node = |name: &str| {
+                *nodes_map
+                    .entry(name.to_owned())
+                    .or_insert_with(|| graph.add_node(name.to_owned()))
+            };
+            let (a, b) = (node(a), node(b));
+            graph.add_edge(a, b, ());
+        }
+        Self { graph, nodes_map }
+    }
+}
+/// One connection per line, in the order they were parsed.
+impl fmt::Display for Caves {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let edges = self.graph.edge_references().map(|edge| {
+            let (a, b) = (&self.graph[edge.source()], &self.graph[edge.target()]);
+            format!("{}-{}", a, b)
+        });
+        write!(f, "{}", edges.format("\n"))
+    }
+}
+pub fn parse(input: &str) -> anyhow::Result<Caves> {
+    let edge = separated_pair(alpha1, cut(char('-')), cut(alpha1));
+    let edges = separated_list1(line_ending, line("cave connection", edge));
+    let parser = map(edges, Caves::new);
+    Ok(parse_all(input, parser)?)
+}
+fn is_big(cave: &str) -> bool {
+    cave.chars().all(|c| c.is_ascii_uppercase())
+}
+fn single_cave_once(graph: &UnGraph<String, ()>, path: &[NodeIndex], cave: NodeIndex) -> bool {
+    is_big(&graph[cave]) || !path.contains(&cave)
+}
+fn single_cave_twice(graph: &UnGraph<String, ()>, path: &[NodeIndex], cave: NodeIndex) -> bool {
+    if graph[cave] == "start" {
+        return false;
+    }
+    if is_big(&graph[cave]) {
+        return true;
+    }
+    !path.contains(&cave) || path.iter().filter(|n| !is_big(&graph[**n])).all_unique()
+}
+fn all_paths(
+    graph: &UnGraph<String, ()>,
+    from: NodeIndex,
+    to: NodeIndex,
+    is_path_selected: impl Fn(&UnGraph<String, ()>, &[NodeIndex], NodeIndex) -> bool,
+) -> Vec<Vec<NodeIndex>> {
+    let mut paths = vec![];
+    let mut stack = vec![vec![from]];
+    while let Some(mut last_path) = stack.pop() {
+        let Some(&last_node) = last_path.last() else {
+            continue;
+        };
+        for neighbor in graph.neighbors(last_node) {
+            if neighbor == to {
+                last_path.push(to);
+                paths.push(last_path.clone());
+                last_path.pop();
+                continue;
+            }
+            if is_path_selected(graph, &last_path, neighbor) {
+                let mut new_path = last_path.clone();
+                new_path.push(neighbor);
+                stack.push(new_path);
+            }
+        }
+    }
+    paths
+}
+pub enum PathSelection {
+    Once,
+    Twice,
+}
+pub fn cave_paths(caves: &Caves, path_selection: PathSelection) -> anyhow::Result<usize> {
+    let from = caves.nodes_map.get("start").context("no start cave")?;
+    let to = caves.nodes_map.get("end").context("no end cave")?;
+    let graph = &caves.graph;
+    if let Some(edge) = graph
+        .edge_references()
+        .find(|edge| is_big(&graph[edge.source()]) && is_big(&graph[edge.target()]))
+    {
+        bail!(
+            "big caves {} and {} are connected, so there are endless paths",
+            graph[edge.source()],
+            graph[edge.target()]
+        );
+    }
+    let paths = match path_selection {
+        PathSelection::Once => all_paths(&caves.graph, *from, *to, single_cave_once),
+        PathSelection::Twice => all_paths(&caves.graph, *from, *to, single_cave_twice),
+    };
+
+    //for path in &paths {
+    //    eprintln!(
+    //        "path = {:?}",
+    //        path.iter().map(|n| caves.graph[*n]).collect::<Vec<_>>()
+    //    );
+    //}
+    Ok(paths.len())
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Parsed = Caves;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        cave_paths(parsed, PathSelection::Once)
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        cave_paths(parsed, PathSelection::Twice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn round_trip(edges in prop::collection::vec(("[a-zA-Z]{1,5}", "[a-zA-Z]{1,5}"), 1..30)) {
+            let text = edges.iter().map(|(a, b)| format!("{}-{}", a, b)).join("\n");
+            prop_assert_eq!(parse(&text).ok().map(|caves| caves.to_string()), Some(text));
+        }
+    }
+    const INPUT1: &str = r#"start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end"#;
+    const INPUT2: &str = r#"dc-end
+HN-start
+start-kj
+dc-start
+dc-HN
+LN-dc
+HN-end
+kj-sa
+kj-HN
+kj-dc"#;
+    const INPUT3: &str = r#"fs-end
+he-DX
+fs-he
+start-DX
+pj-DX
+end-zg
+zg-sl
+zg-pj
+pj-he
+RW-he
+fs-DX
+pj-RW
+zg-RW
+start-pj
+he-WI
+zg-he
+pj-fs
+start-RW"#;
+    #[test]
+    fn part1() -> anyhow::Result<()> {
+        let cave1 = parse(INPUT1)?;
+        let cave2 = parse(INPUT2)?;
+        let cave3 = parse(INPUT3)?;
+        assert_eq!(cave_paths(&cave1, PathSelection::Once)?, 10);
+        assert_eq!(cave_paths(&cave2, PathSelection::Once)?, 19);
+        assert_eq!(cave_paths(&cave3, PathSelection::Once)?, 226);
+        Ok(())
+    }
+    #[test]
+    fn part2() -> anyhow::Result<()> {
+        let cave1 = parse(INPUT1)?;
+        let cave2 = parse(INPUT2)?;
+        let cave3 = parse(INPUT3)?;
+        assert_eq!(cave_paths(&cave1, PathSelection::Twice)?, 36);
+        assert_eq!(cave_paths(&cave2, PathSelection::Twice)?, 103);
+        assert_eq!(cave_paths(&cave3, PathSelection::Twice)?, 3509);
+        Ok(())
+    }
+    #[test]
+    fn unbounded_caves() -> anyhow::Result<()> {
+        let caves = parse("start-A\nA-B\nB-end")?;
+        let err = cave_paths(&caves, PathSelection::Once).unwrap_err();
+        assert!(err.to_string().contains("endless paths"));
+        let caves = parse("start-Ab\nAb-end\nAb-c")?;
+        assert_eq!(cave_paths(&caves, PathSelection::Twice)?, 2);
+        let caves = parse("start-a\na-b")?;
+        assert!(cave_paths(&caves, PathSelection::Once).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{
+    parsing::{line, parse_all},
+    Solution,
+};
+use anyhow::Context;
+use itertools::Itertools;
+use ndarray::Array2;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, line_ending, u32},
+    combinator::{cut, map, value, verify},
+    multi::separated_list1,
+    sequence::separated_pair,
+    Parser,
+};
+/// Coordinates are limited so a hostile input can't make the paper arbitrarily large.
+pub const MAX_COORD: u32 = 4096;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Fold {
+    X(u32),
+    Y(u32),
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaperFolds {
+    paper: Array2<u32>,
+    folds: Vec<Fold>,
+}
+
+impl PaperFolds {
+    fn new(dots: Vec<(u32, u32)>, folds: Vec<Fold>) -> Self {
+        let (bound_x, bound_y) = dots.iter().fold((0, 0), |(max_x, max_y), (x, y)| {
+            (max_x.max(*x), max_y.max(*y))
+        });
+        let mut paper = Array2::zeros((bound_y as usize + 1, bound_x as usize + 1));
+        dots.iter()
+            .copied()
+            .for_each(|(x, y)| paper[(y as usize, x as usize)] = 1);
+        Self { paper, folds }
+    }
+    fn apply(&mut self, fold: Fold) {
+        let (rows, cols) = self.paper.dim();
+        let (new_rows, new_cols) = match fold {
+            Fold::X(pos) => (rows, folded_len(cols, pos as usize)),
+            Fold::Y(pos) => (folded_len(rows, pos as usize), cols),
+        };
+        let mut paper = Array2::zeros((new_rows, new_cols));
+        for ((y, x), &dot) in self.paper.indexed_iter() {
+            let folded = match fold {
+                Fold::X(pos) => folded_index(x, pos as usize, new_cols).map(|x| (y, x)),
+                Fold::Y(pos) => folded_index(y, pos as usize, new_rows).map(|y| (y, x)),
+            };
+            if let Some(idx) = folded {
+                paper[idx] |= dot;
+            }
+        }
+        self.paper = paper;
+    }
+}
+/// Length of an axis of `len` cells once folded at `pos`; the longer half wins.
+fn folded_len(len: usize, pos: usize) -> usize {
+    pos.max(len.saturating_sub(pos + 1))
+}
+/// Where cell `idx` ends up after folding at `pos`, or `None` if it sits on the fold line.
+fn folded_index(idx: usize, pos: usize, new_len: usize) -> Option<usize> {
+    match idx.cmp(&pos) {
+        std::cmp::Ordering::Less => Some(new_len - pos + idx),
+        std::cmp::Ordering::Equal => None,
+        std::cmp::Ordering::Greater => Some(new_len - (idx - pos)),
+    }
+}
+
+pub fn parse(input: &str) -> anyhow::Result<PaperFolds> {
+    let coord = || verify(u32, |&c| c < MAX_COORD);
+    let dot = line("dot", separated_pair(coord(), cut(char(',')), cut(coord())));
+    let dots = separated_list1(line_ending, dot);
+    let gap = line_ending.and(line_ending);
+    let axis = alt((
+        value(Fold::X as fn(u32) -> Fold, char('x')),
+        value(Fold::Y as fn(u32) -> Fold, char('y')),
+    ));
+    let fold = tag("fold along ")
+        .and(cut(separated_pair(axis, char('='), coord())))
+        .map(|(_, (axis, pos))| axis(pos));
+    let folds = separated_list1(line_ending, line("fold instruction", fold));
+
+    let parser = map(dots.and(gap).and(folds), |((dots, _), fold)| {
+        PaperFolds::new(dots, fold)
+    });
+    Ok(parse_all(input, parser)?)
+}
+pub fn dots_after_one_fold(paper_folds: &mut PaperFolds) -> anyhow::Result<u32> {
+    let fold = *paper_folds.folds.first().context("no fold instructions")?;
+    paper_folds.apply(fold);
+    Ok(paper_folds.paper.sum())
+}
+pub fn letters(paper_folds: &PaperFolds) -> String {
+    let mut paper_folds = paper_folds.clone();
+    let folds = paper_folds.folds.clone();
+    for fold in folds {
+        paper_folds.apply(fold);
+    }
+    paper_folds
+        .paper
+        .rows()
+        .into_iter()
+        .map(|row| {
+            row.iter()
+                .map(|c| match c {
+                    1 => '#',
+                    0 => '.',
+                    _ => '!',
+                })
+                .collect::<String>()
+        })
+        .join("\n")
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Parsed = PaperFolds;
+    type Part1 = u32;
+    type Part2 = String;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        dots_after_one_fold(&mut parsed.clone())
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        Ok(format!("\n{}", letters(parsed)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn round_trip(
+            dots in prop::collection::vec((0..300u32, 0..MAX_COORD), 1..50),
+            folds in prop::collection::vec((any::<bool>(), 0..MAX_COORD), 1..10),
+        ) {
+            let folds = folds
+                .into_iter()
+                .map(|(x, pos)| if x { Fold::X(pos) } else { Fold::Y(pos) })
+                .collect_vec();
+            let dots_text = dots.iter().map(|(x, y)| format!("{},{}", x, y)).join("\n");
+            let folds_text = folds
+                .iter()
+                .map(|fold| match fold {
+                    Fold::X(pos) => format!("fold along x={}", pos),
+                    Fold::Y(pos) => format!("fold along y={}", pos),
+                })
+                .join("\n");
+            let text = format!("{}\n\n{}", dots_text, folds_text);
+            prop_assert_eq!(parse(&text).ok(), Some(PaperFolds::new(dots, folds)));
+        }
+    }
+
+    const INPUT: &str = r#"6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5"#;
+    #[test]
+    fn part1() -> anyhow::Result<()> {
+        let mut paper_folds = parse(INPUT)?;
+        assert_eq!(dots_after_one_fold(&mut paper_folds)?, 17);
+        Ok(())
+    }
+    #[test]
+    fn uneven_folds() -> anyhow::Result<()> {
+        let mut paper_folds = parse("0,0\n4,0\n\nfold along x=1\nfold along y=3")?;
+        assert_eq!(dots_after_one_fold(&mut paper_folds)?, 2);
+        assert_eq!(letters(&parse("0,0\n4,0\n\nfold along x=1")?), "#.#");
+        assert_eq!(letters(&parse("0,0\n\nfold along y=3")?), "#\n.\n.");
+        Ok(())
+    }
+}
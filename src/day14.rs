@@ -0,0 +1,216 @@
+use crate::{
+    parsing::{line, parse_all},
+    Solution,
+};
+use anyhow::Context;
+use itertools::{Itertools, MinMaxResult};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, line_ending, satisfy},
+    combinator::{cut, map},
+    multi::separated_list1,
+    sequence::{pair, separated_pair},
+    Parser,
+};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polymerization {
+    formula: String,
+    rules: HashMap<[char; 2], char>,
+    pair_count: HashMap<[char; 2], usize>,
+}
+
+impl Polymerization {
+    fn new(formula: &str, rules: Vec<([char; 2], char)>) -> Self {
+        let rules = rules.into_iter().collect::<HashMap<[char; 2], char>>();
+
+        let mut pair_count = HashMap::new();
+        for (c1, c2) in formula.chars().tuple_windows() {
+            pair_count
+                .entry([c1, c2])
+                .and_modify(|e| *e += 1)
+                .or_insert(1);
+        }
+
+        Polymerization {
+            formula: formula.to_owned(),
+            rules,
+            pair_count,
+        }
+    }
+    pub fn step_naive(&mut self) {
+        let mut new_formula = String::new();
+        new_formula.extend(
+            self.formula
+                .chars()
+                .tuple_windows()
+                .flat_map(|(c1, c2)| std::iter::once(c1).chain(self.rules.get(&[c1, c2]).copied()))
+                .chain(self.formula.chars().rev().take(1)),
+        );
+        self.formula = new_formula;
+    }
+    pub fn step_counting_pairs(&mut self) -> anyhow::Result<()> {
+        let pair_count_new = self.pair_count.clone();
+        for (k @ [first, second], count) in pair_count_new.iter().filter(|(_, v)| **v != 0) {
+            let Some(&mid) = self.rules.get(k) else {
+                continue;
+            };
+            for new_pair in [[*first, mid], [mid, *second]] {
+                let entry = self.pair_count.entry(new_pair).or_insert(0);
+                *entry = entry.checked_add(*count).context("pair count overflowed")?;
+            }
+            self.pair_count.entry(*k).and_modify(|e| *e -= *count);
+        }
+        Ok(())
+    }
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Polymerization> {
+    let formula = line("polymer template", alpha1).map(|s: &str| s.to_owned());
+    let gap = line_ending.and(line_ending);
+    let element = || satisfy(|c| c.is_ascii_alphabetic());
+    let rule = separated_pair(
+        pair(element(), element()).map(|(a, b)| [a, b]),
+        cut(tag(" -> ")),
+        cut(element()),
+    );
+    let rules = separated_list1(line_ending, line("insertion rule", rule));
+
+    let parser = map(formula.and(gap).and(rules), |((formula, _), rules)| {
+        Polymerization::new(&formula, rules)
+    });
+    Ok(parse_all(input, parser)?)
+}
+pub fn polymers_stat_naive(polymers: &Polymerization) -> usize {
+    let counts = polymers.formula.chars().counts();
+    match counts.iter().minmax_by_key(|(_, count)| *count) {
+        MinMaxResult::MinMax(x, y) => y.1 - x.1,
+        _ => 0,
+    }
+}
+pub fn polymers_stat_counting(polymers: &Polymerization) -> anyhow::Result<usize> {
+    // Every element starts exactly one pair, except the last one of the polymer.
+    let mut char_count = HashMap::<char, usize>::new();
+    for ([first, _], count) in &polymers.pair_count {
+        let entry = char_count.entry(*first).or_insert(0);
+        *entry = entry
+            .checked_add(*count)
+            .context("element count overflowed")?;
+    }
+    if let Some(last) = polymers.formula.chars().last() {
+        *char_count.entry(last).or_insert(0) += 1;
+    }
+
+    Ok(match char_count.values().minmax() {
+        MinMaxResult::MinMax(min, max) => max - min,
+        _ => 0,
+    })
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Parsed = Polymerization;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        let mut polymers = parsed.clone();
+        (0..10).for_each(|_| polymers.step_naive());
+        Ok(polymers_stat_naive(&polymers))
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        let mut polymers = parsed.clone();
+        for _ in 0..40 {
+            polymers.step_counting_pairs()?;
+        }
+        polymers_stat_counting(&polymers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn round_trip(
+            formula in "[A-Za-z]{1,30}",
+            rules in prop::collection::vec(("[A-Za-z]{2}", "[A-Za-z]"), 1..30),
+        ) {
+            let text = format!(
+                "{}\n\n{}",
+                formula,
+                rules.iter().map(|(pair, mid)| format!("{} -> {}", pair, mid)).join("\n")
+            );
+            let rules = rules
+                .iter()
+                .map(|(pair, mid)| {
+                    let pair: Vec<char> = pair.chars().collect();
+                    ([pair[0], pair[1]], mid.chars().next().unwrap())
+                })
+                .collect();
+            let expected = Polymerization::new(&formula, rules);
+            prop_assert_eq!(parse(&text).ok(), Some(expected));
+        }
+    }
+    const INPUT: &str = r#"NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C"#;
+    #[test]
+    fn part1() -> anyhow::Result<()> {
+        let mut polymers = parse(INPUT)?;
+        (0..10).for_each(|_| polymers.step_naive());
+        assert_eq!(polymers_stat_naive(&polymers), 1588);
+
+        let mut polymers = parse(INPUT)?;
+        for _ in 0..10 {
+            polymers.step_counting_pairs()?;
+        }
+        assert_eq!(polymers_stat_counting(&polymers)?, 1588);
+        Ok(())
+    }
+    #[test]
+    fn part2() -> anyhow::Result<()> {
+        let mut polymers = parse(INPUT)?;
+        for _ in 0..40 {
+            polymers.step_counting_pairs()?;
+        }
+        assert_eq!(polymers_stat_counting(&polymers)?, 2188189693529);
+        Ok(())
+    }
+    #[test]
+    fn missing_rules() -> anyhow::Result<()> {
+        let mut naive = parse("NBN\n\nNB -> N")?;
+        let mut counting = naive.clone();
+        for _ in 0..3 {
+            naive.step_naive();
+            counting.step_counting_pairs()?;
+        }
+        assert_eq!(naive.formula, "NNNNBN");
+        assert_eq!(polymers_stat_naive(&naive), 4);
+        assert_eq!(polymers_stat_counting(&counting)?, 4);
+        Ok(())
+    }
+}
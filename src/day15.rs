@@ -0,0 +1,166 @@
+use crate::{
+    grid::{char_grid, Grid, Pos},
+    parsing::parse_all,
+    render::{Frame, Pixel, Render},
+    Solution,
+};
+use anyhow::Context;
+use nom::{character::complete::satisfy, Parser};
+use petgraph::{algo::astar, graph::NodeIndex, Graph};
+#[derive(Debug, Clone)]
+pub struct RiskMap {
+    graph: Graph<(), u32>,
+}
+
+impl RiskMap {
+    pub fn new(risks: &Grid<u32>) -> Self {
+        let mut graph = Graph::new();
+        risks.positions().for_each(|_| {
+            graph.add_node(());
+        });
+        risks.positions().for_each(|pos| {
+            risks.neighbours4(pos).for_each(|adj| {
+                graph.add_edge(
+                    NodeIndex::new(risks.index_of(pos)),
+                    NodeIndex::new(risks.index_of(adj)),
+                    risks[adj],
+                );
+            });
+        });
+        Self { graph }
+    }
+    fn a_star_path(&self) -> Option<(u32, Vec<NodeIndex>)> {
+        let finish = NodeIndex::new(self.graph.node_count().checked_sub(1)?);
+        astar(
+            &self.graph,
+            NodeIndex::new(0),
+            |node| node == finish,
+            |e| *e.weight(),
+            |_| 0,
+        )
+    }
+}
+
+/// The positions along a lowest risk path from the top left to the bottom right corner.
+pub fn best_path(risks: &Grid<u32>) -> Option<Vec<Pos>> {
+    let (_, path) = RiskMap::new(risks).a_star_path()?;
+    let width = risks.width();
+    Some(
+        path.iter()
+            .map(|n| (n.index() % width, n.index() / width))
+            .collect(),
+    )
+}
+
+/// The risk map with a path drawn over it.
+pub struct PathOverlay {
+    frame: Frame,
+}
+impl PathOverlay {
+    pub fn new(risks: &Grid<u32>, path: &[Pos]) -> Self {
+        let mut frame = risks.map(|&risk| {
+            let shade = 255 - 25 * risk.min(9) as u8;
+            Pixel {
+                glyph: char::from_digit(risk, 10).unwrap_or('?'),
+                colour: [shade, shade, shade],
+            }
+        });
+        for &pos in path {
+            if let Some(pixel) = frame.get_mut(pos) {
+                *pixel = Pixel {
+                    glyph: '*',
+                    colour: [220, 40, 40],
+                };
+            }
+        }
+        Self { frame }
+    }
+}
+impl Render for PathOverlay {
+    fn render(&self) -> Frame {
+        self.frame.clone()
+    }
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Grid<u32>> {
+    let risk = satisfy(|c| c.is_ascii_digit()).map(|c| c as u32 - '0' as u32);
+    Ok(parse_all(input, char_grid("risk level row", risk))?)
+}
+pub fn tiled(tile: &Grid<u32>, num: usize) -> Grid<u32> {
+    let (w, h) = (tile.width(), tile.height());
+    Grid::from_fn(w * num, h * num, |(x, y)| {
+        let shift = (x / w + y / h) as u32;
+        match shift {
+            0 => tile[(x % w, y % h)],
+            _ => (tile[(x % w, y % h)] + shift - 1) % 9 + 1,
+        }
+    })
+}
+pub fn path_risk_level(input: &RiskMap) -> anyhow::Result<u32> {
+    let (risk, _) = input.a_star_path().context("no path through the cave")?;
+    Ok(risk)
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Parsed = Grid<u32>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        path_risk_level(&RiskMap::new(parsed))
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        path_risk_level(&RiskMap::new(&tiled(parsed, 5)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const INPUT: &str = r#"1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581"#;
+    #[test]
+    fn part1() -> anyhow::Result<()> {
+        let parsed = parse(INPUT)?;
+        let risk_map = RiskMap::new(&parsed);
+        assert_eq!(path_risk_level(&risk_map)?, 40);
+
+        let path = best_path(&parsed).unwrap();
+        assert_eq!((path[0], path[path.len() - 1]), ((0, 0), (9, 9)));
+        let risk: u32 = path[1..].iter().map(|&pos| parsed[pos]).sum();
+        assert_eq!(risk, 40);
+        let overlay = PathOverlay::new(&parsed, &path).render();
+        assert_eq!(
+            overlay.iter().filter(|p| p.glyph == '*').count(),
+            path.len()
+        );
+
+        Ok(())
+    }
+    #[test]
+    fn part2() -> anyhow::Result<()> {
+        let parsed = parse(INPUT)?;
+        let tiled_map = tiled(&parsed, 5);
+        let tiled_map = RiskMap::new(&tiled_map);
+        assert_eq!(path_risk_level(&tiled_map)?, 315);
+
+        let zeros = tiled(&parse("00")?, 2);
+        assert_eq!(zeros[(0, 0)], 0);
+        assert_eq!(zeros[(2, 0)], 1);
+        Ok(())
+    }
+}
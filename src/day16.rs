@@ -0,0 +1,467 @@
+use crate::{parsing::ParseError, Solution};
+use anyhow::{bail, Context};
+use bitvec::{order::Msb0, vec::BitVec};
+use nom::combinator::{map, verify};
+use nom::error::{Error, ErrorKind};
+use nom::multi::{count, many_till};
+use nom::sequence::preceded;
+use nom::{bits::complete::tag, bits::complete::take};
+use nom::{IResult, Parser};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packet {
+    version: u8,
+    kind: PacketKind,
+    data: Option<Vec<Packet>>,
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PacketKind {
+    Sum,
+    Prod,
+    Min,
+    Max,
+    Literal(u64),
+    Gt,
+    Lt,
+    Eq,
+}
+
+type ParseResult<'a, T> = IResult<(&'a [u8], usize), T>;
+
+/// Operator packets nested deeper than this are rejected rather than risking the stack.
+pub const MAX_DEPTH: usize = 512;
+
+fn parse_literal(input: (&[u8], usize)) -> ParseResult<'_, PacketKind> {
+    let group = preceded(tag(0b1, 1usize), take(4usize));
+    let group_last = preceded(tag(0b0, 1usize), take(4usize));
+    // 16 groups of 4 bits fill a u64
+    let groups = verify(
+        many_till(group, group_last),
+        |(groups, _): &(Vec<u8>, u8)| groups.len() < 16,
+    );
+    let mut parser = map(groups, |(groups, last): (Vec<u8>, u8)| {
+        let literal = groups
+            .iter()
+            .chain([&last])
+            .fold(0, |acc, &n| acc << 4 | n as u64);
+        PacketKind::Literal(literal)
+    });
+    parser.parse(input)
+}
+fn parse_subpackets(input: (&[u8], usize), depth: usize) -> ParseResult<'_, Vec<Packet>> {
+    let (input, length_type_id): (_, u8) = take(1usize)(input)?;
+    if length_type_id == 0 {
+        fn remaining_len(input: (&[u8], usize)) -> usize {
+            input.0.len() * 8 - input.1
+        }
+        let (mut input, len) = take(15usize)(input)?;
+        let mut packets = vec![];
+        let mut cur_len = remaining_len(input);
+        let start_len = cur_len;
+        while start_len - cur_len < len {
+            let (inner_input, packet) = Packet::parse_from_offset(input, depth)?;
+            input = inner_input;
+            cur_len = remaining_len(input);
+            packets.push(packet);
+        }
+        Ok((input, packets))
+    } else {
+        let (input, num) = take::<_, usize, _, _>(11usize)(input)?;
+        count(|i| Packet::parse_from_offset(i, depth), num).parse(input)
+    }
+}
+impl Packet {
+    pub fn literal(version: u8, value: u64) -> Self {
+        Self {
+            version: version & 0b111,
+            kind: PacketKind::Literal(value),
+            data: None,
+        }
+    }
+    /// An operator packet; it only parses back if `kind` accepts that many `packets`.
+    pub fn operator(version: u8, kind: PacketKind, packets: Vec<Packet>) -> Self {
+        Self {
+            version: version & 0b111,
+            kind,
+            data: Some(packets),
+        }
+    }
+    pub fn parse(input: &[u8]) -> ParseResult<'_, Packet> {
+        Self::parse_from_offset((input, 0), 0)
+    }
+    fn parse_from_offset(input: (&[u8], usize), depth: usize) -> ParseResult<'_, Self> {
+        let start = input;
+        if depth > MAX_DEPTH {
+            return Err(nom::Err::Failure(Error::new(start, ErrorKind::TooLarge)));
+        }
+        let (input, version) = take(3usize)(input)?;
+        let (mut input, type_id): (_, u8) = take(3usize)(input)?;
+        let kind = match type_id {
+            0 => PacketKind::Sum,
+            1 => PacketKind::Prod,
+            2 => PacketKind::Min,
+            3 => PacketKind::Max,
+            4 => {
+                let (i, literal) = parse_literal(input)?;
+                input = i;
+                literal
+            }
+            5 => PacketKind::Gt,
+            6 => PacketKind::Lt,
+            // the type id is 3 bits wide, so this is 7
+            _ => PacketKind::Eq,
+        };
+        let data = match kind {
+            PacketKind::Literal(_) => None,
+            _ => {
+                let (i, packets) = parse_subpackets(input, depth + 1)?;
+                let operands_ok = match kind {
+                    PacketKind::Min | PacketKind::Max => !packets.is_empty(),
+                    PacketKind::Gt | PacketKind::Lt | PacketKind::Eq => packets.len() == 2,
+                    _ => true,
+                };
+                if !operands_ok {
+                    return Err(nom::Err::Failure(Error::new(start, ErrorKind::Count)));
+                }
+                input = i;
+                Some(packets)
+            }
+        };
+        Ok((
+            input,
+            Packet {
+                version,
+                kind,
+                data,
+            },
+        ))
+    }
+    pub fn version_sum(&self) -> u64 {
+        let packets = self.data.as_deref().unwrap_or_default();
+        packets.iter().map(|p| p.version_sum()).sum::<u64>() + self.version as u64
+    }
+    /// Encodes the packet as a hex transmission, padding the last byte with zero bits.
+    pub fn to_hex(&self) -> String {
+        let mut bits = BitVec::new();
+        self.encode(&mut bits);
+        hex::encode_upper(bits.into_vec())
+    }
+    fn encode(&self, bits: &mut BitVec<u8, Msb0>) {
+        fn push(bits: &mut BitVec<u8, Msb0>, value: u64, width: usize) {
+            bits.extend((0..width).rev().map(|i| value >> i & 1 == 1));
+        }
+        push(bits, self.version as u64, 3);
+        let type_id = match self.kind {
+            PacketKind::Sum => 0,
+            PacketKind::Prod => 1,
+            PacketKind::Min => 2,
+            PacketKind::Max => 3,
+            PacketKind::Literal(n) => {
+                push(bits, 4, 3);
+                let groups = (64 - n.leading_zeros() as usize).div_ceil(4).max(1);
+                for group in (0..groups).rev() {
+                    push(bits, (group > 0) as u64, 1);
+                    push(bits, n >> (group * 4) & 0xF, 4);
+                }
+                return;
+            }
+            PacketKind::Gt => 5,
+            PacketKind::Lt => 6,
+            PacketKind::Eq => 7,
+        };
+        push(bits, type_id, 3);
+        let packets = self.data.as_deref().unwrap_or_default();
+        let mut inner = BitVec::new();
+        packets.iter().for_each(|p| p.encode(&mut inner));
+        if inner.len() < 1 << 15 {
+            push(bits, 0, 1);
+            push(bits, inner.len() as u64, 15);
+        } else {
+            push(bits, 1, 1);
+            push(bits, packets.len() as u64, 11);
+        }
+        bits.extend_from_bitslice(&inner);
+    }
+    pub fn eval(&self) -> anyhow::Result<u64> {
+        let packets = self.data.as_deref().unwrap_or_default();
+        let values = packets
+            .iter()
+            .map(Packet::eval)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(match (&self.kind, values.as_slice()) {
+            (PacketKind::Literal(n), _) => *n,
+            (PacketKind::Sum, _) => values
+                .iter()
+                .try_fold(0u64, |acc, v| acc.checked_add(*v))
+                .context("sum overflowed")?,
+            (PacketKind::Prod, _) => values
+                .iter()
+                .try_fold(1u64, |acc, v| acc.checked_mul(*v))
+                .context("product overflowed")?,
+            (PacketKind::Min, _) => *values.iter().min().context("minimum of no packets")?,
+            (PacketKind::Max, _) => *values.iter().max().context("maximum of no packets")?,
+            (PacketKind::Gt, [a, b]) => (a > b) as u64,
+            (PacketKind::Lt, [a, b]) => (a < b) as u64,
+            (PacketKind::Eq, [a, b]) => (a == b) as u64,
+            (kind, _) => bail!("{:?} needs exactly two packets, got {}", kind, values.len()),
+        })
+    }
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Packet> {
+    let start = input.len() - input.trim_start().len();
+    let bytes = hex::decode(input.trim()).map_err(|e| match e {
+        hex::FromHexError::InvalidHexCharacter { index, .. } => ParseError::at(
+            input,
+            start + index,
+            "expected a hex digit",
+            vec!["transmission"],
+        )
+        .into(),
+        e => anyhow::Error::from(e),
+    })?;
+    let (_, packet) = Packet::parse(&bytes).map_err(|e| match e {
+        nom::Err::Error(e) | nom::Err::Failure(e) => {
+            let (rest, bit) = e.input;
+            let offset = (bytes.len() - rest.len()) * 8 + bit;
+            let reason = match e.code {
+                ErrorKind::Verify => ": literal does not fit in 64 bits",
+                ErrorKind::Count => ": wrong number of operands",
+                ErrorKind::TooLarge => ": packets nested too deeply",
+                _ => "",
+            };
+            anyhow::anyhow!(
+                "malformed packet at bit {} of the transmission{}",
+                offset,
+                reason
+            )
+        }
+        nom::Err::Incomplete(_) => anyhow::anyhow!("truncated packet"),
+    })?;
+    Ok(packet)
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    type Parsed = Packet;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        Ok(parsed.version_sum())
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        parsed.eval()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+    use proptest::prelude::*;
+
+    fn packet() -> impl Strategy<Value = Packet> {
+        let literal = (0..8u8, any::<u64>()).prop_map(|(version, n)| Packet {
+            version,
+            kind: PacketKind::Literal(n),
+            data: None,
+        });
+        literal.prop_recursive(4, 64, 4, |inner| {
+            (0..8u8, 0..7usize, prop::collection::vec(inner, 1..5)).prop_map(
+                |(version, kind, mut packets)| {
+                    use PacketKind::*;
+                    let kind = [Sum, Prod, Min, Max, Gt, Lt, Eq][kind].clone();
+                    if matches!(kind, Gt | Lt | Eq) {
+                        packets.resize(2, packets[0].clone());
+                    }
+                    Packet {
+                        version,
+                        kind,
+                        data: Some(packets),
+                    }
+                },
+            )
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(packet in packet()) {
+            prop_assert_eq!(parse(&packet.to_hex()).ok(), Some(packet));
+        }
+    }
+    #[test]
+    fn hostile() {
+        let nested = (0..=MAX_DEPTH).fold(
+            Packet {
+                version: 0,
+                kind: PacketKind::Literal(1),
+                data: None,
+            },
+            |packet, _| Packet {
+                version: 0,
+                kind: PacketKind::Sum,
+                data: Some(vec![packet]),
+            },
+        );
+        let err = parse(&nested.to_hex()).unwrap_err();
+        assert!(err.to_string().ends_with("packets nested too deeply"));
+        // a 17 nibble literal, and a comparison with a single operand
+        let err = parse("13FFFFFFFFFFFFFFFFFFFC20").unwrap_err();
+        assert!(err.to_string().ends_with("literal does not fit in 64 bits"));
+        let err = parse("16004408").unwrap_err();
+        assert!(err.to_string().ends_with("wrong number of operands"));
+        let gt = Packet::operator(0, PacketKind::Gt, vec![Packet::literal(0, 1)]);
+        assert!(gt.eval().is_err());
+        let prod = Packet::operator(0, PacketKind::Prod, vec![Packet::literal(0, u64::MAX); 2]);
+        assert!(prod.eval().is_err());
+    }
+
+    #[test]
+    fn part1_1() -> anyhow::Result<()> {
+        const PACKET: [u8; 3] = hex!("D2FE28");
+        let (_, packet) = Packet::parse(&PACKET)?;
+        assert_eq!(
+            packet,
+            Packet {
+                version: 6,
+                kind: PacketKind::Literal(2021),
+                data: None,
+            }
+        );
+        Ok(())
+    }
+    #[test]
+    fn part1_2() -> anyhow::Result<()> {
+        let (_, op_packet) = Packet::parse(&hex!("38006F45291200"))?;
+        assert_eq!(
+            op_packet,
+            Packet {
+                version: 1,
+                kind: PacketKind::Lt,
+                data: Some(vec![
+                    Packet {
+                        version: 6,
+                        kind: PacketKind::Literal(10),
+                        data: None,
+                    },
+                    Packet {
+                        version: 2,
+                        kind: PacketKind::Literal(20),
+                        data: None,
+                    },
+                ]),
+            }
+        );
+        Ok(())
+    }
+    #[test]
+    fn part1_3() -> anyhow::Result<()> {
+        let (_, op_packet) = Packet::parse(&hex!("EE00D40C823060"))?;
+        assert_eq!(
+            op_packet,
+            Packet {
+                version: 7,
+                kind: PacketKind::Max,
+                data: Some(vec![
+                    Packet {
+                        version: 2,
+                        kind: PacketKind::Literal(1),
+                        data: None,
+                    },
+                    Packet {
+                        version: 4,
+                        kind: PacketKind::Literal(2),
+                        data: None,
+                    },
+                    Packet {
+                        version: 1,
+                        kind: PacketKind::Literal(3),
+                        data: None,
+                    },
+                ],)
+            }
+        );
+
+        Ok(())
+    }
+    #[test]
+    fn part1_4() -> anyhow::Result<()> {
+        let (_, packet) = Packet::parse(&hex!("8A004A801A8002F478"))?;
+        assert_eq!(packet.version_sum(), 16);
+        Ok(())
+    }
+    #[test]
+    fn part1_5() -> anyhow::Result<()> {
+        let (_, packet) = Packet::parse(&hex!("620080001611562C8802118E34"))?;
+        assert_eq!(packet.version_sum(), 12);
+        Ok(())
+    }
+    #[test]
+    fn part1_6() -> anyhow::Result<()> {
+        let (_, packet) = Packet::parse(&hex!("C0015000016115A2E0802F182340"))?;
+        assert_eq!(packet.version_sum(), 23);
+        Ok(())
+    }
+    #[test]
+    fn part1_7() -> anyhow::Result<()> {
+        let (_, packet) = Packet::parse(&hex!("A0016C880162017C3686B18A3D4780"))?;
+        assert_eq!(packet.version_sum(), 31);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_1() -> anyhow::Result<()> {
+        let (_, packet) = Packet::parse(&hex!("C200B40A82"))?;
+        assert_eq!(packet.eval()?, 3);
+        Ok(())
+    }
+    #[test]
+    fn part2_2() -> anyhow::Result<()> {
+        let (_, packet) = Packet::parse(&hex!("04005AC33890"))?;
+        assert_eq!(packet.eval()?, 54);
+        Ok(())
+    }
+    #[test]
+    fn part2_3() -> anyhow::Result<()> {
+        let (_, packet) = Packet::parse(&hex!("880086C3E88112"))?;
+        assert_eq!(packet.eval()?, 7);
+        Ok(())
+    }
+    #[test]
+    fn part2_4() -> anyhow::Result<()> {
+        let (_, packet) = Packet::parse(&hex!("CE00C43D881120"))?;
+        assert_eq!(packet.eval()?, 9);
+        Ok(())
+    }
+    #[test]
+    fn part2_5() -> anyhow::Result<()> {
+        let (_, packet) = Packet::parse(&hex!("D8005AC2A8F0"))?;
+        assert_eq!(packet.eval()?, 1);
+        Ok(())
+    }
+    #[test]
+    fn part2_6() -> anyhow::Result<()> {
+        let (_, packet) = Packet::parse(&hex!("F600BC2D8F"))?;
+        assert_eq!(packet.eval()?, 0);
+        Ok(())
+    }
+    #[test]
+    fn part2_7() -> anyhow::Result<()> {
+        let (_, packet) = Packet::parse(&hex!("9C005AC2F8F0"))?;
+        assert_eq!(packet.eval()?, 0);
+        Ok(())
+    }
+    #[test]
+    fn part2_8() -> anyhow::Result<()> {
+        let (_, packet) = Packet::parse(&hex!("9C0141080250320F1802104A08"))?;
+        assert_eq!(packet.eval()?, 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{parsing::parse_all, Solution};
+use anyhow::Context;
+use itertools::Itertools;
+use nom::{
+    bytes::complete::tag,
+    character::complete::i32,
+    combinator::verify,
+    error::context,
+    sequence::{preceded, separated_pair},
+    Parser,
+};
+use std::{collections::HashSet, fmt, ops::RangeInclusive};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetArea {
+    x_range: RangeInclusive<i32>,
+    y_range: RangeInclusive<i32>,
+}
+impl fmt::Display for TargetArea {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let TargetArea {
+            x_range: x,
+            y_range: y,
+        } = self;
+        write!(
+            f,
+            "target area: x={}..{}, y={}..{}",
+            x.start(),
+            x.end(),
+            y.start(),
+            y.end()
+        )
+    }
+}
+
+/// The target has to lie within this distance of the probe, to the right of and below it.
+pub const MAX_COORD: i32 = 1000;
+
+pub fn parse(input: &str) -> anyhow::Result<TargetArea> {
+    let range = |bounds: RangeInclusive<i32>| {
+        verify(separated_pair(i32, tag(".."), i32), move |(lo, hi)| {
+            bounds.contains(lo) && lo <= hi && bounds.contains(hi)
+        })
+    };
+    let x_range = context("x range", preceded(tag("x="), range(1..=MAX_COORD)));
+    let y_range = context("y range", preceded(tag("y="), range(-MAX_COORD..=-1)));
+    let ranges = separated_pair(x_range, tag(", "), y_range);
+    let parser =
+        context("target area", preceded(tag("target area: "), ranges)).map(|(x, y)| TargetArea {
+            x_range: x.0..=x.1,
+            y_range: y.0..=y.1,
+        });
+    Ok(parse_all(input, parser)?)
+}
+pub fn max_height(ta: TargetArea) -> anyhow::Result<i32> {
+    let vels = possible_velocities(ta);
+    let max_y = vels
+        .iter()
+        .map(|(_, y)| *y)
+        .max()
+        .context("no velocity hits the target")?;
+    Ok(max_y * (max_y + 1) / 2)
+}
+pub fn possible_velocities_count(ta: TargetArea) -> usize {
+    possible_velocities(ta).len()
+}
+fn possible_velocities(ta: TargetArea) -> HashSet<(i32, i32)> {
+    let mut vel_xs: Vec<RangeInclusive<i32>> = (1..)
+        .map(|n| {
+            let left = ta.x_range.start() + n * (n - 1) / 2;
+            let right = ta.x_range.end() + n * (n - 1) / 2;
+            let ceil = (left.rem_euclid(n) != 0) as i32;
+            let start_vel_x_range = (left.div_euclid(n) + ceil)..=right.div_euclid(n);
+            (n, start_vel_x_range)
+        })
+        .take_while(|(n, _)| *ta.x_range.start() > n * (n - 1) / 2)
+        .map(|(_, start_vel_x_range)| start_vel_x_range)
+        .collect();
+    let vel_ys: Vec<RangeInclusive<i32>> = (1i32..)
+        .map(|n| {
+            let bottom = ta.y_range.start() + n * (n - 1) / 2;
+            let top = ta.y_range.end() + n * (n - 1) / 2;
+            let ceil = (bottom.rem_euclid(n) != 0) as i32;
+            let start_vel_y_range = (bottom.div_euclid(n) + ceil)..=top.div_euclid(n);
+            (n, start_vel_y_range)
+        })
+        .take_while(|(n, start_vel_y_range)| {
+            let end_vel = start_vel_y_range.end() - (n - 1);
+            end_vel >= *ta.y_range.start()
+        })
+        .map(|(_, start_vel_y_range)| start_vel_y_range)
+        .collect();
+    if let Some(last_x) = vel_xs.last().cloned() {
+        vel_xs.resize(vel_ys.len(), last_x);
+    }
+    vel_xs
+        .into_iter()
+        .zip(vel_ys)
+        .flat_map(move |(xr, yr)| xr.cartesian_product(yr))
+        .collect()
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    type Parsed = TargetArea;
+    type Part1 = i32;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        max_height(parsed.clone())
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        Ok(possible_velocities_count(parsed.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn range(bounds: RangeInclusive<i32>) -> impl Strategy<Value = RangeInclusive<i32>> {
+        (bounds.clone(), bounds).prop_map(|(a, b)| a.min(b)..=a.max(b))
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(x_range in range(1..=MAX_COORD), y_range in range(-MAX_COORD..=-1)) {
+            let area = TargetArea { x_range, y_range };
+            prop_assert_eq!(parse(&area.to_string()).ok(), Some(area));
+        }
+    }
+    const INPUT: &str = "target area: x=20..30, y=-10..-5";
+
+    #[test]
+    fn part1() -> anyhow::Result<()> {
+        let target_area = parse(INPUT)?;
+        assert_eq!(max_height(target_area)?, 45);
+        Ok(())
+    }
+    #[test]
+    fn out_of_reach() {
+        assert!(parse("target area: x=-30..-20, y=-10..-5").is_err());
+        assert!(parse("target area: x=30..20, y=-10..-5").is_err());
+        assert!(parse("target area: x=20..30, y=5..10").is_err());
+        assert!(parse("target area: x=20..30, y=-1001..-5").is_err());
+    }
+    #[test]
+    fn part2() -> anyhow::Result<()> {
+        let target_area = parse(INPUT)?;
+        assert_eq!(possible_velocities_count(target_area), 112);
+        Ok(())
+    }
+}
@@ -0,0 +1,536 @@
+use crate::{
+    parsing::{line, parse_all, IResult, ParseError},
+    Solution,
+};
+use anyhow::Context;
+use indextree::{Arena, NodeId};
+use itertools::Itertools;
+use nom::{
+    branch::alt,
+    character::complete::{char, line_ending, multispace0, u8},
+    combinator::cut,
+    error::{ErrorKind, ParseError as _, VerboseError},
+    multi::separated_list1,
+    sequence::{delimited, preceded, separated_pair},
+    Parser,
+};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::str::FromStr;
+
+/// Pairs nested deeper than this are rejected when parsing, which bounds the parser's recursion.
+const MAX_DEPTH: usize = 5;
+
+#[derive(Debug, Clone)]
+pub struct FishNum {
+    arena: Arena<Option<u32>>,
+    root: Option<NodeId>,
+}
+
+enum Element {
+    Regular(u8),
+    Pair(Box<Element>, Box<Element>),
+}
+
+fn element(input: &str, depth: usize) -> IResult<'_, Element> {
+    alt((u8.map(Element::Regular), |i| pair(i, depth + 1)))(input)
+}
+fn pair(input: &str, depth: usize) -> IResult<'_, Element> {
+    if depth > MAX_DEPTH {
+        let error = VerboseError::from_error_kind(input, ErrorKind::TooLarge);
+        return Err(nom::Err::Failure(error));
+    }
+    let elements = separated_pair(
+        |i| element(i, depth),
+        cut(char(',')),
+        cut(|i| element(i, depth)),
+    );
+    delimited(char('['), elements, cut(char(']')))
+        .map(|(left, right)| Element::Pair(Box::new(left), Box::new(right)))
+        .parse(input)
+}
+
+impl FishNum {
+    /// Parses a snailfish number.
+    ///
+    /// # Panics
+    /// If `input` isn't a valid snailfish number; use [`str::parse`] for untrusted input.
+    pub fn new(input: &str) -> Self {
+        input.parse().expect("valid snailfish number")
+    }
+    fn from_element(element: &Element) -> Self {
+        let mut this = Self {
+            arena: Arena::new(),
+            root: None,
+        };
+        this.root = Some(this.append_element(element));
+        this
+    }
+    fn append_element(&mut self, element: &Element) -> NodeId {
+        match element {
+            Element::Regular(n) => self.new_node(Some(*n as u32)),
+            Element::Pair(left, right) => {
+                let node = self.new_node(None);
+                let left = self.append_element(left);
+                node.append(left, self);
+                let right = self.append_element(right);
+                node.append(right, self);
+                node
+            }
+        }
+    }
+    /// Copies the subtree of `other` under `node` into this arena.
+    fn graft(&mut self, other: &FishNum, node: NodeId) -> NodeId {
+        let copy = self.new_node(*other[node].get());
+        for child in node.children(other) {
+            let child = self.graft(other, child);
+            copy.append(child, self);
+        }
+        copy
+    }
+    fn split(&mut self) -> bool {
+        let split_node = self
+            .root
+            .expect("some root")
+            .descendants(self)
+            .find(|id| self[*id].get().gt(&Some(9)));
+
+        match split_node {
+            Some(split_node) => {
+                let num = self[split_node]
+                    .get_mut()
+                    .take()
+                    .expect("leaf nodes are values");
+                let left = self.new_node(Some(num / 2));
+                let right = self.new_node(Some(num / 2 + (num % 2)));
+                split_node.append(left, self);
+                split_node.append(right, self);
+                true
+            }
+            None => false,
+        }
+    }
+    fn explode(&mut self) -> bool {
+        let Some(explode_node) = self.explode_node(self.root.expect("some root"), 4) else {
+            return false;
+        };
+        let nums: Vec<u32> = explode_node
+            .children(self)
+            .map(|child| (*self[child].get()).expect("leaf node"))
+            .collect();
+        for (leaf, num) in [
+            (self.neighbour_leaf(explode_node, true), nums[0]),
+            (self.neighbour_leaf(explode_node, false), nums[1]),
+        ] {
+            if let Some(leaf) = leaf {
+                *self[leaf]
+                    .get_mut()
+                    .as_mut()
+                    .expect("leaf nodes are values") += num;
+            }
+        }
+        explode_node.insert_before(self.new_node(Some(0)), self);
+        explode_node.remove_subtree(self);
+        true
+    }
+    pub fn reduce(&mut self) {
+        while self.explode() || self.split() {}
+    }
+    pub fn magnitude(&self) -> u32 {
+        self.magnitude_node(self.root.expect("some root"))
+    }
+    fn magnitude_node(&self, node: NodeId) -> u32 {
+        if let Some(num) = self[node].get() {
+            return *num;
+        }
+        let left = node.children(self).next().expect("left child");
+        let right = node.children(self).nth(1).expect("right child");
+        3 * self.magnitude_node(left) + 2 * self.magnitude_node(right)
+    }
+    /// The leftmost pair of two regular numbers nested inside `height` or more pairs.
+    ///
+    /// Sums of reduced numbers only ever nest such pairs exactly four deep, but a sum of deeper
+    /// ones can nest pairs further still; those are exploded innermost first.
+    fn explode_node(&self, node: NodeId, height: i32) -> Option<NodeId> {
+        if self[node].get().is_some() {
+            return None;
+        }
+        if height <= 0 && node.children(self).all(|child| self[child].get().is_some()) {
+            return Some(node);
+        }
+        node.children(self)
+            .find_map(|child| self.explode_node(child, height - 1))
+    }
+    /// The regular number nearest to the left (or right) of `node`, however deep it's nested.
+    fn neighbour_leaf(&self, node: NodeId, left: bool) -> Option<NodeId> {
+        let mut leaf = node.ancestors(self).find_map(|node| {
+            if left {
+                self[node].previous_sibling()
+            } else {
+                self[node].next_sibling()
+            }
+        })?;
+        while let Some(child) = if left {
+            self[leaf].last_child()
+        } else {
+            self[leaf].first_child()
+        } {
+            leaf = child;
+        }
+        Some(leaf)
+    }
+    fn format_node(&self, node: NodeId, s: &mut String) {
+        s.push('[');
+        if let Some(num) = self[node].get() {
+            s.push_str(&u32::to_string(num));
+            s.push(']');
+            return;
+        }
+        let mut first = true;
+        for child in node.children(self) {
+            if first {
+                first = false;
+            } else {
+                s.push(',');
+            }
+            if let Some(num) = self[child].get() {
+                s.push_str(&u32::to_string(num));
+            } else {
+                self.format_node(child, s);
+            }
+        }
+        s.push(']');
+    }
+}
+impl std::ops::Deref for FishNum {
+    type Target = Arena<Option<u32>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.arena
+    }
+}
+impl std::ops::DerefMut for FishNum {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.arena
+    }
+}
+impl std::fmt::Display for FishNum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = String::new();
+        self.format_node(self.root.expect("some root"), &mut s);
+        write!(f, "{s}")
+    }
+}
+impl std::ops::AddAssign for FishNum {
+    fn add_assign(&mut self, rhs: Self) {
+        let new_root = self.new_node(None);
+        new_root.append(self.root.expect("some root"), self);
+        self.root = Some(new_root);
+        let rhs_root = self.graft(&rhs, rhs.root.expect("some root"));
+        new_root.append(rhs_root, self);
+    }
+}
+impl FromStr for FishNum {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_all(input, |i| pair(i, 1)).map(|element| Self::from_element(&element))
+    }
+}
+pub fn parse(input: &str) -> anyhow::Result<Vec<FishNum>> {
+    let num = line("snailfish number", |i| pair(i, 1)).map(|e| FishNum::from_element(&e));
+    let nums = separated_list1(line_ending, num);
+    Ok(parse_all(input, preceded(multispace0, nums))?)
+}
+pub fn sum_magnitude(nums: &[FishNum]) -> anyhow::Result<u32> {
+    let sum = nums
+        .iter()
+        .cloned()
+        .reduce(|mut acc, num| {
+            acc += num;
+            acc.reduce();
+            acc
+        })
+        .context("no snailfish numbers")?;
+    Ok(sum.magnitude())
+}
+/// Sums every ordered pair, on the rayon thread pool with the `parallel` feature.
+pub fn largest_pair_magnitude(nums: &[FishNum]) -> anyhow::Result<u32> {
+    let magnitude = |pair: Vec<&FishNum>| {
+        let mut sum = pair[0].clone();
+        sum += pair[1].clone();
+        sum.reduce();
+        sum.magnitude()
+    };
+    #[cfg(feature = "parallel")]
+    let largest = {
+        let pairs: Vec<_> = nums.iter().permutations(2).collect();
+        pairs.into_par_iter().map(magnitude).max()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let largest = nums.iter().permutations(2).map(magnitude).max();
+    largest.context("need at least two snailfish numbers")
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    type Parsed = Vec<FishNum>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        sum_magnitude(parsed)
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        largest_pair_magnitude(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use proptest::prelude::*;
+
+    fn fish_num() -> impl Strategy<Value = String> {
+        (0..10u8)
+            .prop_map(|n| n.to_string())
+            .prop_recursive(MAX_DEPTH as u32 - 1, 32, 2, |inner| {
+                (inner.clone(), inner).prop_map(|(l, r)| format!("[{},{}]", l, r))
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(left in fish_num(), right in fish_num()) {
+            let input = format!("[{},{}]", left, right);
+            prop_assert_eq!(input.parse::<FishNum>().map(|n| n.to_string()), Ok(input));
+        }
+    }
+    #[test]
+    fn parse_error() {
+        let err = parse("[1,2]\n[[3,4],5,6]").unwrap_err();
+        let err = err.downcast_ref::<ParseError>().unwrap();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (2, 9, "expected ']'")
+        );
+        let err = "[[[[[[1,2],3],4],5],6],7]".parse::<FishNum>().unwrap_err();
+        assert_eq!(
+            (err.column, err.expected.as_str()),
+            (6, "too deeply nested")
+        );
+    }
+    #[test]
+    fn add() {
+        let mut num = FishNum::new("[[1,2],3]");
+        let num2 = FishNum::new("[4,5]");
+        num += num2;
+        assert_eq!("[[[1,2],3],[4,5]]", num.to_string());
+    }
+    #[test]
+    fn split() {
+        let mut num = FishNum::new("[11,[1,1]]");
+        num.split();
+        assert_eq!(num.to_string(), FishNum::new("[[5,6],[1,1]]").to_string());
+        let mut num = FishNum::new("[[1,1],11]");
+        num.split();
+        assert_eq!(num.to_string(), FishNum::new("[[1,1],[5,6]]").to_string());
+        let mut num = FishNum::new("[[11,11],1]");
+        num.split();
+        assert_eq!(num.to_string(), FishNum::new("[[[5,6],11],1]").to_string());
+        num.split();
+        assert_eq!(
+            num.to_string(),
+            FishNum::new("[[[5,6],[5,6]],1]").to_string()
+        );
+    }
+    #[test]
+    fn explode() {
+        let mut num = FishNum::new("[[[[[9,8],1],2],3],4]");
+        num.explode();
+        assert_eq!(num.to_string(), "[[[[0,9],2],3],4]");
+
+        let mut num = FishNum::new("[7,[6,[5,[4,[3,2]]]]]");
+        num.explode();
+        assert_eq!(num.to_string(), "[7,[6,[5,[7,0]]]]");
+
+        let mut num = FishNum::new("[[6,[5,[4,[3,2]]]],1]");
+        num.explode();
+        assert_eq!(num.to_string(), "[[6,[5,[7,0]]],3]");
+
+        let mut num = FishNum::new("[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]");
+        num.explode();
+        assert_eq!(num.to_string(), "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]");
+
+        let mut num = FishNum::new("[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]");
+        num.explode();
+        assert_eq!(num.to_string(), "[[3,[2,[8,0]]],[9,[5,[7,0]]]]");
+
+        let mut num = FishNum::new("[[[[0,7],4],[7,[[8,4],9]]],[1,1]]");
+        num.explode();
+        assert_eq!(num.to_string(), "[[[[0,7],4],[15,[0,13]]],[1,1]]");
+
+        let mut num = FishNum::new("[[[[0,7],4],[[7,8],[0,[6,7]]]],[1,1]]");
+        num.explode();
+        assert_eq!(num.to_string(), "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]");
+    }
+    #[test]
+    fn reduce() {
+        let mut num = FishNum::new("[[[[4,3],4],4],[7,[[8,4],9]]]");
+        num += FishNum::new("[1,1]");
+        num.reduce();
+        assert_eq!(num.to_string(), "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]");
+
+        let mut num = FishNum::new("[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]");
+        num += FishNum::new("[7,[[[3,7],[4,3]],[[6,3],[8,8]]]]");
+        num.reduce();
+        assert_eq!(
+            num.to_string(),
+            "[[[[4,0],[5,4]],[[7,7],[6,0]]],[[8,[7,7]],[[7,9],[5,0]]]]"
+        );
+    }
+    #[test]
+    fn multi() {
+        let input = r#"
+[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]
+[7,[[[3,7],[4,3]],[[6,3],[8,8]]]]
+[[2,[[0,8],[3,4]]],[[[6,7],1],[7,[1,6]]]]
+[[[[2,4],7],[6,[0,5]]],[[[6,8],[2,8]],[[2,1],[4,5]]]]
+[7,[5,[[3,8],[1,4]]]]
+[[2,[2,2]],[8,[8,1]]]
+[2,9]
+[1,[[[9,3],9],[[9,0],[0,7]]]]
+[[[5,[7,4]],7],1]
+[[[[4,2],2],6],[8,7]]
+        "#
+        .trim();
+        let result = input
+            .split('\n')
+            .map(FishNum::new)
+            .reduce(|mut acc, num| {
+                acc += num;
+                acc.reduce();
+                acc
+            })
+            .unwrap();
+
+        assert_eq!(
+            result.to_string(),
+            "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]"
+        );
+    }
+    #[test]
+    fn magnitude() {
+        assert_eq!(FishNum::new("[[1,2],[[3,4],5]]").magnitude(), 143);
+        assert_eq!(
+            FishNum::new("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]").magnitude(),
+            1384
+        );
+        assert_eq!(
+            FishNum::new("[[[[1,1],[2,2]],[3,3]],[4,4]]").magnitude(),
+            445
+        );
+        assert_eq!(
+            FishNum::new("[[[[3,0],[5,3]],[4,4]],[5,5]]").magnitude(),
+            791
+        );
+        assert_eq!(
+            FishNum::new("[[[[5,0],[7,4]],[5,5]],[6,6]]").magnitude(),
+            1137
+        );
+        assert_eq!(
+            FishNum::new("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]").magnitude(),
+            3488
+        );
+    }
+    #[test]
+    fn final_example() {
+        let input = r#"
+[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+[[[[5,4],[7,7]],8],[[8,3],8]]
+[[9,3],[[9,9],[6,[4,9]]]]
+[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]
+        "#
+        .trim();
+        let result = input
+            .split('\n')
+            .map(FishNum::new)
+            .reduce(|mut acc, num| {
+                acc += num;
+                acc.reduce();
+                acc
+            })
+            .unwrap();
+
+        assert_eq!(result.magnitude(), 4140);
+        assert_eq!(sum_magnitude(&parse(input).unwrap()).unwrap(), 4140);
+    }
+    #[test]
+    fn part2() {
+        let input = r#"
+[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+[[[[5,4],[7,7]],8],[[8,3],8]]
+[[9,3],[[9,9],[6,[4,9]]]]
+[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]
+        "#
+        .trim();
+
+        let result = input
+            .split('\n')
+            .map(FishNum::new)
+            .permutations(2)
+            .map(|pair| {
+                pair.into_iter()
+                    .reduce(|mut acc, num| {
+                        acc += num;
+                        acc.reduce();
+                        acc
+                    })
+                    .unwrap()
+                    .magnitude()
+            })
+            .max()
+            .unwrap();
+
+        assert_eq!(result, 3993);
+        assert_eq!(
+            largest_pair_magnitude(&parse(input).unwrap()).unwrap(),
+            3993
+        );
+        assert!(largest_pair_magnitude(&parse("[1,2]").unwrap()).is_err());
+    }
+    #[test]
+    fn deeper_than_reduced() -> anyhow::Result<()> {
+        // five deep parses, and adding it nests a pair six deep
+        let nums = parse("[[[[[1,2],3],4],5],6]\n[1,1]")?;
+        let mut sum = nums[0].clone();
+        sum += nums[1].clone();
+        sum.reduce();
+        assert_eq!(sum.to_string(), "[[[[0,9],5],6],[1,1]]");
+        assert_eq!(sum_magnitude(&nums)?, 622);
+        assert!(largest_pair_magnitude(&nums).is_ok());
+        // both neighbours of a pair five deep pick up its numbers
+        let mut num = FishNum::new("[1,[[[2,[3,4]],5],6]]");
+        num.reduce();
+        assert_eq!(num.to_string(), "[1,[[[5,0],9],6]]");
+        Ok(())
+    }
+}
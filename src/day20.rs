@@ -0,0 +1,201 @@
+use crate::{
+    grid::{char_grid, Grid},
+    parsing::{line, parse_all},
+    render::{Frame, Pixel, Render},
+    Solution,
+};
+use anyhow::{bail, ensure};
+use bitvec::vec::BitVec;
+use nom::{
+    character::complete::{char, line_ending},
+    combinator::{map, verify},
+    multi::many1,
+    sequence::tuple,
+    Parser,
+};
+use std::fmt::{Display, Write};
+
+#[derive(Debug, Clone)]
+pub struct Image {
+    data: Grid<bool>,
+}
+impl Image {
+    pub fn bordered(&self, border_size: usize) -> Image {
+        let w = self.data.width();
+        let h = self.data.height();
+        let data = Grid::from_fn(w + 2 * border_size, h + 2 * border_size, |(x, y)| {
+            x.checked_sub(border_size)
+                .zip(y.checked_sub(border_size))
+                .and_then(|pos| self.data.get(pos))
+                .copied()
+                .unwrap_or(false)
+        });
+        Image { data }
+    }
+}
+impl Display for Image {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.data.rows() {
+            for lit in row {
+                f.write_char(if *lit { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+impl Render for Image {
+    fn render(&self) -> Frame {
+        self.data.map(|&lit| Pixel {
+            glyph: if lit { '#' } else { '.' },
+            colour: if lit { [240, 240, 220] } else { [10, 10, 20] },
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ParsedInput {
+    ieas: BitVec,
+    input: Image,
+}
+
+pub fn parse(input: &str) -> anyhow::Result<ParsedInput> {
+    let pixel = |i| {
+        char('.')
+            .map(|_| false)
+            .or(char('#').map(|_| true))
+            .parse(i)
+    };
+    let gap = line_ending.and(line_ending);
+    // one output pixel for each of the 2^9 neighbourhoods of a 3x3 kernel
+    let ieas = line(
+        "enhancement algorithm",
+        verify(many1(pixel), |ieas: &Vec<_>| ieas.len() == 1 << 9),
+    )
+    .map(BitVec::from_iter);
+    let image = char_grid("image row", pixel);
+    let parser = map(tuple((ieas, gap, image)), |(ieas, _, data)| ParsedInput {
+        ieas,
+        input: Image { data },
+    });
+    Ok(parse_all(input, parser)?)
+}
+/// One pass of the algorithm; the image shrinks by `kernel_size - 1` pixels each way.
+pub fn image_enhancement_algo(
+    image: &Image,
+    ieas: &BitVec,
+    kernel_size: usize,
+) -> anyhow::Result<Image> {
+    let neighbourhoods = (kernel_size * kernel_size)
+        .try_into()
+        .ok()
+        .and_then(|bits| 1usize.checked_shl(bits));
+    ensure!(
+        kernel_size > 0 && neighbourhoods == Some(ieas.len()),
+        "a {0}x{0} kernel doesn't match an algorithm of {1} pixels",
+        kernel_size,
+        ieas.len()
+    );
+    let width = (image.data.width() + 1).checked_sub(kernel_size);
+    let height = (image.data.height() + 1).checked_sub(kernel_size);
+    let (Some(width), Some(height)) = (width, height) else {
+        bail!("the image is smaller than the {0}x{0} kernel", kernel_size);
+    };
+    let data = Grid::from_fn(width, height, |(x, y)| {
+        let idx = (0..kernel_size)
+            .flat_map(|dy| (0..kernel_size).map(move |dx| (x + dx, y + dy)))
+            .fold(0, |acc, pos| acc << 1 | image.data[pos] as usize);
+        ieas[idx]
+    });
+    Ok(Image { data })
+}
+pub fn lit_pixels(image: &Image) -> usize {
+    image.data.iter().filter(|lit| **lit).count()
+}
+pub fn enhanced_lit_pixels(parsed: &ParsedInput, runs: usize) -> anyhow::Result<usize> {
+    let kernel_size = 3;
+    let mut image = parsed.input.bordered(runs * (kernel_size - 1));
+    for _ in 0..runs {
+        image = image_enhancement_algo(&image, &parsed.ieas, kernel_size)?;
+    }
+    Ok(lit_pixels(&image))
+}
+/// The bordered input image, then the image after each of `runs` passes.
+pub fn enhancement_passes(parsed: ParsedInput, runs: usize) -> impl Iterator<Item = Image> {
+    let kernel_size = 3;
+    let image = parsed.input.bordered(runs * (kernel_size - 1));
+    std::iter::successors(Some(image), move |image| {
+        image_enhancement_algo(image, &parsed.ieas, kernel_size).ok()
+    })
+    .take(runs + 1)
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+    type Parsed = ParsedInput;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input.trim())
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        enhanced_lit_pixels(parsed, 2)
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        enhanced_lit_pixels(parsed, 50)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    const INPUT: &str = r#"..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+
+#..#.
+#....
+##..#
+..#..
+..###"#;
+
+    #[test]
+    fn part1() -> anyhow::Result<()> {
+        let parsed = parse(INPUT)?;
+        let runs = 2;
+        let kernel_size = 3;
+        let mut image = parsed.input.bordered(runs * (kernel_size - 1));
+        for _ in 0..runs {
+            image = image_enhancement_algo(&image, &parsed.ieas, kernel_size)?;
+        }
+        eprintln!("{}", image.bordered(2));
+        assert_eq!(lit_pixels(&image), 35);
+        Ok(())
+    }
+    #[test]
+    fn part2() -> anyhow::Result<()> {
+        let parsed = parse(INPUT)?;
+        let runs = 50;
+        let kernel_size = 3;
+        let mut image = parsed.input.bordered(runs * (kernel_size - 1));
+        for _ in 0..runs {
+            image = image_enhancement_algo(&image, &parsed.ieas, kernel_size)?;
+        }
+        eprintln!("{}", image);
+        assert_eq!(lit_pixels(&image), 3351);
+        Ok(())
+    }
+    #[test]
+    fn mismatched_kernel() -> anyhow::Result<()> {
+        let parsed = parse(INPUT)?;
+        assert!(image_enhancement_algo(&parsed.input, &parsed.ieas, 2).is_err());
+        assert!(image_enhancement_algo(&parsed.input, &parsed.ieas, 9).is_err());
+        let tiny = parsed.input.bordered(0);
+        let tiny = image_enhancement_algo(&tiny, &parsed.ieas, 3)?;
+        let tiny = image_enhancement_algo(&tiny, &parsed.ieas, 3)?;
+        assert!(image_enhancement_algo(&tiny, &parsed.ieas, 3).is_err());
+        Ok(())
+    }
+}
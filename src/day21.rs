@@ -0,0 +1,216 @@
+use crate::{
+    parsing::{line, parse_all},
+    Solution,
+};
+use anyhow::{bail, ensure, Context};
+use itertools::Itertools;
+use nom::{
+    bytes::complete::{tag, take_until},
+    character::complete::{line_ending, u32},
+    combinator::{cut, verify},
+    multi::separated_list0,
+    sequence::preceded,
+    Parser,
+};
+use once_cell::unsync::OnceCell;
+use std::{cell::RefCell, collections::HashMap};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Player {
+    pos: u32,
+    score: u32,
+    id: usize,
+}
+
+impl Player {
+    fn turn(mut self, roll: u32) -> Self {
+        self.pos = (self.pos + roll - 1) % 10 + 1;
+        self.score += self.pos;
+        self
+    }
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Vec<Player>> {
+    let position = preceded(
+        take_until(": ").and(tag(": ")),
+        cut(verify(u32, |pos| (1..=10).contains(pos))),
+    );
+    let parser =
+        separated_list0(line_ending, line("starting position", position)).map(|positions| {
+            positions
+                .into_iter()
+                .enumerate()
+                .map(|(id, pos)| Player { pos, id, score: 0 })
+                .collect()
+        });
+    Ok(parse_all(input, parser)?)
+}
+pub struct Dice {
+    win_score: u32,
+    rolls_per_turn: usize,
+    dice_sides: u32,
+    players: Vec<Player>,
+    rolls: OnceCell<HashMap<u32, usize>>,
+    cache: RefCell<HashMap<GameState, [u64; 2]>>,
+}
+#[derive(PartialEq, Eq, Hash)]
+struct GameState((Player, Player));
+
+impl Dice {
+    pub fn new(
+        win_score: u32,
+        rolls_per_turn: usize,
+        dice_sides: u32,
+        players: Vec<Player>,
+    ) -> Self {
+        Self {
+            win_score,
+            rolls_per_turn,
+            dice_sides,
+            players,
+            rolls: OnceCell::new(),
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+    pub fn determministic_rolls(&mut self) -> anyhow::Result<usize> {
+        ensure!(!self.players.is_empty(), "no players");
+        ensure!(
+            self.rolls_per_turn > 0 && self.dice_sides > 0,
+            "the die has to be rolled at least once and have at least one side"
+        );
+        let players = (0..self.players.len()).cycle();
+        let rolls = (1..=self.dice_sides).cycle().chunks(self.rolls_per_turn);
+        let roll_count = players
+            .zip(&rolls)
+            .map(|(i, roll)| {
+                self.players[i] = self.players[i].turn(roll.sum());
+                self.players[i].score
+            })
+            .take_while(|score| score < &self.win_score)
+            .count()
+            + 1;
+        Ok(roll_count * self.rolls_per_turn)
+    }
+    pub fn looser_score(&self) -> anyhow::Result<usize> {
+        let score = self
+            .players
+            .iter()
+            .map(|player| player.score)
+            .min()
+            .context("no players")?;
+        Ok(score as usize)
+    }
+    fn rolls_freq(&self) -> &HashMap<u32, usize> {
+        self.rolls.get_or_init(|| {
+            (0..self.rolls_per_turn)
+                .map(|_| 1..=self.dice_sides)
+                .multi_cartesian_product()
+                .map(|rolls| rolls.into_iter().sum())
+                .counts()
+        })
+    }
+    pub fn dirac_winner_counts(&self) -> anyhow::Result<[u64; 2]> {
+        let [p1, p2] = self.players[..] else {
+            bail!(
+                "the Dirac dice game needs two players, not {}",
+                self.players.len()
+            );
+        };
+        Ok(self.universe(p1, p2))
+    }
+
+    fn universe(&self, p1: Player, p2: Player) -> [u64; 2] {
+        if p2.score >= self.win_score {
+            return [0, 1];
+        }
+        let state = GameState((p1, p2));
+        if self.cache.borrow().contains_key(&state) {
+            return self.cache.borrow()[&state];
+        }
+
+        let wins = self
+            .rolls_freq()
+            .iter()
+            .fold([0, 0], |mut acc, (&roll, &freq)| {
+                let [w0, w1] = self.universe(p2, p1.turn(roll));
+                acc[0] += w1 * freq as u64;
+                acc[1] += w0 * freq as u64;
+                acc
+            });
+        self.cache.borrow_mut().insert(state, wins);
+        wins
+    }
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+    type Parsed = Vec<Player>;
+    type Part1 = usize;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        let mut game = Dice::new(1000, 3, 100, parsed.clone());
+        Ok(game.determministic_rolls()? * game.looser_score()?)
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        let game = Dice::new(21, 3, 3, parsed.clone());
+        let [wins1, wins2] = game.dirac_winner_counts()?;
+        Ok(wins1.max(wins2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn round_trip(positions in prop::collection::vec(1..=10u32, 1..5)) {
+            let text = positions
+                .iter()
+                .enumerate()
+                .map(|(i, pos)| format!("Player {} starting position: {}", i + 1, pos))
+                .join("\n");
+            let players = parse(&text).map(|players| players.iter().map(|p| p.pos).collect_vec());
+            prop_assert_eq!(players.ok(), Some(positions));
+        }
+    }
+    const INPUT: &str = r#"Player 1 starting position: 4
+Player 2 starting position: 8
+"#;
+
+    #[test]
+    fn part1() -> anyhow::Result<()> {
+        let players = parse(INPUT.trim())?;
+        let mut game = Dice::new(1000, 3, 100, players);
+        let part1 = game.determministic_rolls()? * game.looser_score()?;
+        assert_eq!(part1, 739785);
+        Ok(())
+    }
+    #[test]
+    fn part2() -> anyhow::Result<()> {
+        let players = parse(INPUT)?;
+        let game = Dice::new(21, 3, 3, players);
+        let part2 = game.dirac_winner_counts()?;
+        assert_eq!(part2[0], 444_356_092_776_315);
+        Ok(())
+    }
+    #[test]
+    fn odd_games() -> anyhow::Result<()> {
+        let players = parse(INPUT.lines().next().unwrap_or_default())?;
+        assert!(Dice::new(21, 3, 3, players.clone())
+            .dirac_winner_counts()
+            .is_err());
+        assert!(Dice::new(1000, 0, 100, players)
+            .determministic_rolls()
+            .is_err());
+        assert!(Dice::new(1000, 3, 100, vec![]).looser_score().is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,400 @@
+use crate::{parsing::parse_all, Solution};
+use anyhow::Context;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, line_ending, space0},
+    combinator::value,
+    error::context,
+    multi::{many1, separated_list1},
+    sequence::{delimited, preceded, terminated, tuple},
+    Parser,
+};
+use std::cmp::{max, min};
+use std::ops::Not;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Amphipod {
+    Amber,
+    Bronze,
+    Copper,
+    Desert,
+}
+impl Amphipod {
+    fn home(&self) -> usize {
+        match self {
+            Amphipod::Amber => 0,
+            Amphipod::Bronze => 1,
+            Amphipod::Copper => 2,
+            Amphipod::Desert => 3,
+        }
+    }
+    fn energy(&self) -> usize {
+        match self {
+            Amphipod::Amber => 1,
+            Amphipod::Bronze => 10,
+            Amphipod::Copper => 100,
+            Amphipod::Desert => 1000,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tile {
+    Empty,
+    Entrance(usize),
+    Occupied(Amphipod),
+}
+impl std::fmt::Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "."),
+            Self::Entrance(_) => write!(f, "_"),
+            Self::Occupied(amphi) => write!(
+                f,
+                "{}",
+                match amphi {
+                    Amphipod::Amber => 'A',
+                    Amphipod::Bronze => 'B',
+                    Amphipod::Copper => 'C',
+                    Amphipod::Desert => 'D',
+                }
+            ),
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Burrow<const N: usize> {
+    hallway: [Tile; 11],
+    rooms: [[Tile; N]; 4],
+}
+
+impl<const N: usize> std::fmt::Display for Burrow<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{:#<13}", "")?;
+        write!(f, "#")?;
+        for t in self.hallway {
+            write!(f, "{}", t)?;
+        }
+        writeln!(f, "#")?;
+        for c in 0..self.rooms[0].len() {
+            write!(f, "###")?;
+            for r in 0..self.rooms.len() {
+                write!(f, "{}#", self.rooms[r][c])?;
+            }
+            writeln!(f, "##")?;
+        }
+        writeln!(f, "{:^13}", "#######")
+    }
+}
+
+/// The hallway tile just outside a room.
+fn entrance(room: usize) -> usize {
+    2 + 2 * room
+}
+
+impl<const N: usize> Burrow<N> {
+    pub fn new(rooms: [[Tile; N]; 4]) -> Self {
+        let mut hallway = [Tile::Empty; 11];
+        for i in 0..rooms.len() {
+            hallway[entrance(i)] = Tile::Entrance(i);
+        }
+
+        Self { hallway, rooms }
+    }
+    fn required_state(&self) -> bool {
+        self.rooms
+            == [
+                [Tile::Occupied(Amphipod::Amber); N],
+                [Tile::Occupied(Amphipod::Bronze); N],
+                [Tile::Occupied(Amphipod::Copper); N],
+                [Tile::Occupied(Amphipod::Desert); N],
+            ]
+    }
+    pub fn min_cost(&self) -> Option<usize> {
+        if self.required_state() {
+            return Some(0);
+        }
+        let to_room_iter = self
+            .hallway
+            .iter()
+            .enumerate()
+            .filter_map(|(pos, tile)| match tile {
+                Tile::Empty => None,
+                Tile::Entrance(_) => None,
+                Tile::Occupied(amphi) => self.try_move_to_room(pos, *amphi),
+            });
+        let to_hallway_iter = self
+            .rooms
+            .iter()
+            .enumerate()
+            .filter_map(|(room_num, room)| {
+                room.iter()
+                    .all(|tile| match tile {
+                        Tile::Empty => true,
+                        Tile::Occupied(amphi) if room_num == amphi.home() => true,
+                        _ => false,
+                    })
+                    .not()
+                    .then(|| {
+                        room.iter()
+                            .position(|tile| *tile != Tile::Empty)
+                            .and_then(|room_pos| self.try_move_to_hall(room_num, room_pos))
+                    })
+                    .flatten()
+            })
+            .flatten();
+
+        to_room_iter
+            .chain(to_hallway_iter)
+            .filter_map(|(cost, next_state)| next_state.min_cost().map(|min_cost| cost + min_cost))
+            .min()
+    }
+    fn try_move_to_hall(
+        &self,
+        room_num: usize,
+        room_pos: usize,
+    ) -> Option<Vec<(usize, Burrow<N>)>> {
+        let amphi = if let Tile::Occupied(amphi) = self.rooms[room_num][room_pos] {
+            amphi
+        } else {
+            return None;
+        };
+        let room_entrance = entrance(room_num);
+        let left_path = (0..room_entrance)
+            .rev()
+            .filter(|&i| !matches!(self.hallway[i], Tile::Entrance(_)))
+            .take_while(|&i| self.hallway[i] == Tile::Empty);
+        let right_path = (room_entrance + 1..self.hallway.len())
+            .filter(|&i| !matches!(self.hallway[i], Tile::Entrance(_)))
+            .take_while(|&i| self.hallway[i] == Tile::Empty);
+        Some(
+            left_path
+                .chain(right_path)
+                .map(|i| {
+                    let hall_path = min(room_entrance, i)..max(room_entrance, i);
+                    let energy_cost = (hall_path.len() + room_pos + 1) * amphi.energy();
+                    let mut new_burrow = *self;
+                    std::mem::swap(
+                        &mut new_burrow.hallway[i],
+                        &mut new_burrow.rooms[room_num][room_pos],
+                    );
+                    (energy_cost, new_burrow)
+                })
+                .collect(),
+        )
+    }
+    fn try_move_to_room(&self, hall_pos: usize, amphi: Amphipod) -> Option<(usize, Burrow<N>)> {
+        let home = amphi.home();
+        let home_entrance = entrance(home);
+        let mut hall_path = min(home_entrance, hall_pos)..max(home_entrance, hall_pos);
+        let hall_path_len = hall_path.len();
+        if hall_path.any(|i| i != hall_pos && matches!(self.hallway[i], Tile::Occupied(_))) {
+            return None; //Path is blocked
+        }
+        let room_pos = self.rooms[home]
+            .iter()
+            .all(|tile| match tile {
+                Tile::Empty => true,
+                Tile::Occupied(other) if home == other.home() => true,
+                _ => false,
+            })
+            .then(|| {
+                self.rooms[home]
+                    .iter()
+                    .position(|tile| *tile == Tile::Empty)
+            })
+            .flatten()?;
+        let energy_cost = (hall_path_len + room_pos + 1) * amphi.energy();
+        let mut new_burrow = *self;
+        std::mem::swap(
+            &mut new_burrow.hallway[hall_pos],
+            &mut new_burrow.rooms[amphi.home()][room_pos],
+        );
+
+        Some((energy_cost, new_burrow))
+    }
+}
+
+impl Burrow<2> {
+    pub fn unfold(&self) -> Burrow<4> {
+        use Amphipod::*;
+        const FOLDED: [[Amphipod; 2]; 4] = [
+            [Desert, Desert],
+            [Copper, Bronze],
+            [Bronze, Amber],
+            [Amber, Copper],
+        ];
+        let mut rooms = [[Tile::Empty; 4]; 4];
+        for (room, (&[top, bottom], folded)) in rooms.iter_mut().zip(self.rooms.iter().zip(FOLDED))
+        {
+            *room = [
+                top,
+                Tile::Occupied(folded[0]),
+                Tile::Occupied(folded[1]),
+                bottom,
+            ];
+        }
+        Burrow::new(rooms)
+    }
+}
+
+pub fn parse<const N: usize>(input: &str) -> anyhow::Result<Burrow<N>> {
+    let amphipod = |i| {
+        alt((
+            value(Amphipod::Amber, char('A')),
+            value(Amphipod::Bronze, char('B')),
+            value(Amphipod::Copper, char('C')),
+            value(Amphipod::Desert, char('D')),
+        ))
+        .map(Tile::Occupied)
+        .parse(i)
+    };
+    let hallway = context(
+        "hallway",
+        tuple((
+            tag("#############"),
+            line_ending,
+            tag("#...........#"),
+            line_ending,
+        )),
+    );
+    let row = context(
+        "room row",
+        delimited(
+            space0.and(many1(char('#'))),
+            separated_list1(char('#'), amphipod),
+            many1(char('#')),
+        ),
+    );
+    let bottom = context("bottom wall", line_ending.and(space0).and(many1(char('#'))));
+    let parser = preceded(
+        hallway,
+        terminated(separated_list1(line_ending, row), bottom),
+    );
+    let rows = parse_all(input, parser)?;
+    anyhow::ensure!(
+        rows.len() == N && rows.iter().all(|row| row.len() == 4),
+        "expected {} rows of 4 amphipods",
+        N
+    );
+    let mut rooms = [[Tile::Empty; N]; 4];
+    for (pos, row) in rows.into_iter().enumerate() {
+        for (room, tile) in row.into_iter().enumerate() {
+            rooms[room][pos] = tile;
+        }
+    }
+    Ok(Burrow::new(rooms))
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+    type Parsed = Burrow<2>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        parsed.min_cost().context("no way to organize amphipods")
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        parsed
+            .unfold()
+            .min_cost()
+            .context("no way to organize amphipods")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use proptest::prelude::*;
+
+    /// `rooms` laid out the way the puzzle input draws them.
+    fn input_text<const N: usize>(rooms: &[[Amphipod; N]; 4]) -> String {
+        let mut text = String::from("#############\n#...........#\n");
+        for pos in 0..N {
+            let row: Vec<String> = rooms
+                .iter()
+                .map(|room| Tile::Occupied(room[pos]).to_string())
+                .collect();
+            let (left, right) = if pos == 0 {
+                ("###", "###")
+            } else {
+                ("  #", "#")
+            };
+            text += &format!("{}{}{}\n", left, row.join("#"), right);
+        }
+        text + "  #########"
+    }
+
+    fn amphipod() -> impl Strategy<Value = Amphipod> {
+        use Amphipod::*;
+        prop::sample::select(vec![Amber, Bronze, Copper, Desert])
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(rooms in prop::array::uniform4(prop::array::uniform2(amphipod()))) {
+            let expected = Burrow::new(rooms.map(|room| room.map(Tile::Occupied)));
+            prop_assert_eq!(parse::<2>(&input_text(&rooms)).ok(), Some(expected));
+        }
+        #[test]
+        fn round_trip_unfolded(rooms in prop::array::uniform4(prop::array::uniform4(amphipod()))) {
+            let expected = Burrow::new(rooms.map(|room| room.map(Tile::Occupied)));
+            prop_assert_eq!(parse::<4>(&input_text(&rooms)).ok(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn part1() -> anyhow::Result<()> {
+        #[rustfmt::skip]
+        let rooms = [
+            [Tile::Occupied(Amphipod::Bronze), Tile::Occupied(Amphipod::Amber)],
+            [Tile::Occupied(Amphipod::Copper), Tile::Occupied(Amphipod::Desert)],
+            [Tile::Occupied(Amphipod::Bronze), Tile::Occupied(Amphipod::Copper)],
+            [Tile::Occupied(Amphipod::Desert), Tile::Occupied(Amphipod::Amber)],
+        ];
+        let burrow = Burrow::new(rooms);
+        let min = burrow.min_cost();
+        assert_eq!(min, Some(12521));
+        Ok(())
+    }
+    #[test]
+    fn part2() -> anyhow::Result<()> {
+        let rooms = [
+            [
+                Tile::Occupied(Amphipod::Bronze),
+                Tile::Occupied(Amphipod::Desert),
+                Tile::Occupied(Amphipod::Desert),
+                Tile::Occupied(Amphipod::Amber),
+            ],
+            [
+                Tile::Occupied(Amphipod::Copper),
+                Tile::Occupied(Amphipod::Copper),
+                Tile::Occupied(Amphipod::Bronze),
+                Tile::Occupied(Amphipod::Desert),
+            ],
+            [
+                Tile::Occupied(Amphipod::Bronze),
+                Tile::Occupied(Amphipod::Bronze),
+                Tile::Occupied(Amphipod::Amber),
+                Tile::Occupied(Amphipod::Copper),
+            ],
+            [
+                Tile::Occupied(Amphipod::Desert),
+                Tile::Occupied(Amphipod::Amber),
+                Tile::Occupied(Amphipod::Copper),
+                Tile::Occupied(Amphipod::Amber),
+            ],
+        ];
+        let burrow = Burrow::new(rooms);
+        let min = burrow.min_cost();
+        assert_eq!(min, Some(44169));
+        Ok(())
+    }
+}
@@ -0,0 +1,363 @@
+use crate::{
+    parsing::{line, parse_all},
+    Solution,
+};
+use anyhow::{ensure, Context};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, i32, line_ending, space1},
+    combinator::{cut, value},
+    multi::separated_list1,
+    sequence::tuple,
+    Parser,
+};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Inp(Var),
+    Add(Var, VarOrValue),
+    Mul(Var, VarOrValue),
+    Div(Var, VarOrValue),
+    Mod(Var, VarOrValue),
+    Eql(Var, VarOrValue),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Var {
+    W,
+    X,
+    Y,
+    Z,
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarOrValue {
+    Var(Var),
+    Value(i32),
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::Inp(a) => write!(f, "inp {}", a),
+            Instruction::Add(a, b) => write!(f, "add {} {}", a, b),
+            Instruction::Mul(a, b) => write!(f, "mul {} {}", a, b),
+            Instruction::Div(a, b) => write!(f, "div {} {}", a, b),
+            Instruction::Mod(a, b) => write!(f, "mod {} {}", a, b),
+            Instruction::Eql(a, b) => write!(f, "eql {} {}", a, b),
+        }
+    }
+}
+impl Display for Var {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Var::W => 'w',
+            Var::X => 'x',
+            Var::Y => 'y',
+            Var::Z => 'z',
+        };
+        write!(f, "{}", name)
+    }
+}
+impl Display for VarOrValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VarOrValue::Var(var) => write!(f, "{}", var),
+            VarOrValue::Value(val) => write!(f, "{}", val),
+        }
+    }
+}
+
+pub type Program = Vec<Instruction>;
+#[derive(Debug, Clone)]
+pub struct Alu {
+    programs: Vec<Program>,
+}
+impl Alu {
+    pub fn new(program: &str) -> anyhow::Result<Self> {
+        let instructions = parse(program)?;
+        ensure!(
+            matches!(instructions.first(), Some(Instruction::Inp(_))),
+            "the program must start with an inp instruction"
+        );
+        let mut programs: Vec<Program> = vec![];
+        for i in instructions.into_iter() {
+            match i {
+                Instruction::Inp(_) => {
+                    programs.push(vec![i]);
+                }
+                _ => programs.last_mut().into_iter().for_each(|p| p.push(i)),
+            };
+        }
+        Ok(Self { programs })
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Memory {
+    data: [i32; 4],
+}
+impl From<[i32; 4]> for Memory {
+    fn from(data: [i32; 4]) -> Self {
+        Self { data }
+    }
+}
+impl Index<Var> for Memory {
+    type Output = i32;
+
+    fn index(&self, index: Var) -> &Self::Output {
+        match index {
+            Var::W => &self.data[0],
+            Var::X => &self.data[1],
+            Var::Y => &self.data[2],
+            Var::Z => &self.data[3],
+        }
+    }
+}
+impl IndexMut<Var> for Memory {
+    fn index_mut(&mut self, index: Var) -> &mut Self::Output {
+        match index {
+            Var::W => &mut self.data[0],
+            Var::X => &mut self.data[1],
+            Var::Y => &mut self.data[2],
+            Var::Z => &mut self.data[3],
+        }
+    }
+}
+impl Memory {
+    fn get(&self, v: VarOrValue) -> i32 {
+        match v {
+            VarOrValue::Var(var) => self[var],
+            VarOrValue::Value(val) => val,
+        }
+    }
+    fn eval<I>(mut self, inst: Instruction, input: &mut I) -> Option<Self>
+    where
+        I: Iterator<Item = i32>,
+    {
+        match inst {
+            Instruction::Inp(v) => {
+                self[v] = input.next()?;
+            }
+            Instruction::Add(op1, op2) => {
+                self[op1] = self[op1].checked_add(self.get(op2))?;
+            }
+            Instruction::Mul(op1, op2) => {
+                self[op1] = self[op1].checked_mul(self.get(op2))?;
+            }
+            Instruction::Div(op1, op2) => {
+                self[op1] = self[op1].checked_div(self.get(op2))?;
+            }
+            Instruction::Mod(op1, op2) => {
+                (self[op1] >= 0 && self.get(op2) > 0).then_some(())?;
+                self[op1] %= self.get(op2);
+            }
+            Instruction::Eql(op1, op2) => {
+                self[op1] = if self[op1] == self.get(op2) { 1 } else { 0 };
+            }
+        };
+        Some(self)
+    }
+    fn exec(self, program: &Program, input: &[i32]) -> Option<Self> {
+        let mut input_it = input.iter().cloned();
+        program.iter().copied().try_fold(self, |acc, instruction| {
+            acc.eval(instruction, &mut input_it)
+        })
+    }
+}
+pub fn parse(input: &str) -> anyhow::Result<Program> {
+    type Binary = fn(Var, VarOrValue) -> Instruction;
+    let kind = alt((
+        value(Instruction::Add as Binary, tag("add")),
+        value(Instruction::Mul as Binary, tag("mul")),
+        value(Instruction::Div as Binary, tag("div")),
+        value(Instruction::Mod as Binary, tag("mod")),
+        value(Instruction::Eql as Binary, tag("eql")),
+    ));
+    let var = |i| {
+        alt((
+            value(Var::W, char('w')),
+            value(Var::X, char('x')),
+            value(Var::Y, char('y')),
+            value(Var::Z, char('z')),
+        ))(i)
+    };
+    let val = i32.map(VarOrValue::Value);
+    let var_or_val = alt((var.map(VarOrValue::Var), val));
+    let inp = tuple((tag("inp"), cut(space1), cut(var))).map(|(_, _, var)| Instruction::Inp(var));
+    let other = tuple((kind, cut(space1), cut(var), cut(space1), cut(var_or_val)))
+        .map(|(kind, _, var, _, var_or_val)| kind(var, var_or_val));
+    let instruction = line("instruction", alt((inp, other)));
+    let parser = separated_list1(line_ending, instruction);
+    Ok(parse_all(input, parser)?)
+}
+/// Partial model numbers `monad` will branch into; a real MONAD nests its digit checks at most
+/// seven deep, so it never needs more than `9^8`.
+pub const MAX_CANDIDATES: usize = 9usize.pow(8);
+
+pub fn monad(program: &Program, numbers: Vec<(u64, Memory)>) -> anyhow::Result<Vec<(u64, Memory)>> {
+    use Var::*;
+
+    ensure!(
+        numbers.len() <= MAX_CANDIDATES / 9,
+        "more than {} candidate model numbers",
+        MAX_CANDIDATES
+    );
+    let branch = |(number, mem): (u64, Memory)| {
+        (1..10).filter_map(move |input| {
+            let result_mem = mem.exec(program, &[input])?;
+            let number = number.checked_mul(10)?.checked_add(input as u64)?;
+            Some((number, result_mem))
+        })
+    };
+    // Collecting keeps the order of `numbers`, so the result doesn't depend on the thread count.
+    #[cfg(feature = "parallel")]
+    let branches: Vec<_> = numbers.into_par_iter().flat_map_iter(branch).collect();
+    #[cfg(not(feature = "parallel"))]
+    let branches: Vec<_> = numbers.into_iter().flat_map(branch).collect();
+    let (valid, other): (Vec<_>, Vec<_>) = branches.into_iter().partition(|(_, mem)| mem[X] == 0);
+
+    Ok(if !valid.is_empty() { valid } else { other })
+}
+pub fn model_numbers(alu: &Alu) -> anyhow::Result<Vec<(u64, Memory)>> {
+    let mut model_number = vec![(0, Memory::default())];
+    for program in &alu.programs {
+        model_number = monad(program, model_number)?;
+    }
+    Ok(model_number)
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+    type Parsed = Alu;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Alu::new(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        model_numbers(parsed)?
+            .last()
+            .map(|(number, _)| *number)
+            .context("no valid model number")
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        model_numbers(parsed)?
+            .first()
+            .map(|(number, _)| *number)
+            .context("no valid model number")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
+    fn instruction() -> impl Strategy<Value = Instruction> {
+        let var = prop_oneof![Just(Var::W), Just(Var::X), Just(Var::Y), Just(Var::Z)];
+        let var_or_val = prop_oneof![
+            var.clone().prop_map(VarOrValue::Var),
+            any::<i32>().prop_map(VarOrValue::Value)
+        ];
+        let binary: [fn(Var, VarOrValue) -> Instruction; 5] = [
+            Instruction::Add,
+            Instruction::Mul,
+            Instruction::Div,
+            Instruction::Mod,
+            Instruction::Eql,
+        ];
+        prop_oneof![
+            var.clone().prop_map(Instruction::Inp),
+            (0..binary.len(), var, var_or_val).prop_map(move |(k, a, b)| binary[k](a, b)),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(program in prop::collection::vec(instruction(), 1..50)) {
+            let text = program.iter().join("\n");
+            prop_assert_eq!(parse(&text).ok(), Some(program));
+        }
+    }
+    #[test]
+    fn parse_error() {
+        assert!(Alu::new("add x 1\ninp w").is_err());
+    }
+
+    const INPUT1: &str = r#"
+inp x
+mul x -1
+    "#;
+    const INPUT2: &str = r#"
+inp z
+inp x
+mul z 3
+eql z x
+    "#;
+    const INPUT3: &str = r#"
+inp w
+add z w
+mod z 2
+div w 2
+add y w
+mod y 2
+div w 2
+add x w
+mod x 2
+div w 2
+mod w 2
+    "#;
+    const INPUT4: &str = r#"
+inp x
+div x y
+    "#;
+    #[test]
+    fn exec() -> anyhow::Result<()> {
+        let prog1 = parse(INPUT1.trim())?;
+        let prog2 = parse(INPUT2.trim())?;
+        let prog3 = parse(INPUT3.trim())?;
+        let prog4 = parse(INPUT4.trim())?;
+        assert_eq!(
+            Memory::default().exec(&prog1, &[2]),
+            Some(Memory::from([0, -2, 0, 0]))
+        );
+        assert_eq!(
+            Memory::default().exec(&prog2, &[1, 1]),
+            Some(Memory::from([0, 1, 0, 0]))
+        );
+        assert_eq!(
+            Memory::default().exec(&prog2, &[1, 3]),
+            Some(Memory::from([0, 3, 0, 1]))
+        );
+        assert_eq!(
+            Memory::default().exec(&prog3, &[15]),
+            Some(Memory::from([1, 1, 1, 1]))
+        );
+        assert_eq!(Memory::default().exec(&prog4, &[1]), None);
+        let overflow = parse("inp x\nadd x 2147483647")?;
+        assert_eq!(Memory::default().exec(&overflow, &[1]), None);
+        Ok(())
+    }
+    #[test]
+    fn unconstrained() -> anyhow::Result<()> {
+        let program = parse("inp w\nadd x 1")?;
+        let numbers = vec![(0, Memory::default()); MAX_CANDIDATES / 9 + 1];
+        assert!(monad(&program, numbers).is_err());
+        let alu = Alu::new(&["inp w\nadd x 1"; 3].join("\n"))?;
+        let numbers = model_numbers(&alu)?;
+        assert_eq!(numbers.len(), 729);
+        assert_eq!(numbers.last().map(|(number, _)| *number), Some(999));
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+use crate::{
+    grid::{char_grid, Grid},
+    parsing::parse_all,
+    render::{Frame, Pixel, Render},
+    Solution,
+};
+use anyhow::ensure;
+use nom::{character::complete::one_of, Parser};
+use std::fmt::{self, Display};
+
+/// `steps_until_stopped` gives up after this many steps.
+pub const MAX_STEPS: usize = 100_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cucumber {
+    EastFacing,
+    SouthFacing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Occupied(Cucumber),
+    Empty,
+}
+impl Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Tile::Occupied(Cucumber::EastFacing) => '>',
+            Tile::Occupied(Cucumber::SouthFacing) => 'v',
+            Tile::Empty => '.',
+        };
+        write!(f, "{}", c)
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Map {
+    tiles: Grid<Tile>,
+}
+impl Map {
+    fn new(tiles: Grid<Tile>) -> Self {
+        Self {
+            tiles: tiles.wrapping(true),
+        }
+    }
+    pub fn step(self) -> Self {
+        self.step_hor().step_vert()
+    }
+
+    fn step_hor(self) -> Self {
+        self.step_herd(Cucumber::EastFacing, (1, 0))
+    }
+    fn step_vert(self) -> Self {
+        self.step_herd(Cucumber::SouthFacing, (0, 1))
+    }
+    /// Moves every cucumber of `herd` one tile along `(dx, dy)` if that tile is empty.
+    fn step_herd(self, herd: Cucumber, (dx, dy): (isize, isize)) -> Self {
+        let grid = &self.tiles;
+        let tiles = Grid::from_fn(grid.width(), grid.height(), |pos| {
+            let tile = grid[pos];
+            let ahead = grid[grid.offset(pos, (dx, dy)).expect("wrapping grid")];
+            let behind = grid[grid.offset(pos, (-dx, -dy)).expect("wrapping grid")];
+            if tile == Tile::Occupied(herd) && ahead == Tile::Empty {
+                Tile::Empty
+            } else if behind == Tile::Occupied(herd) && tile == Tile::Empty {
+                Tile::Occupied(herd)
+            } else {
+                tile
+            }
+        });
+        Self::new(tiles)
+    }
+    pub fn iter(self) -> MapIter {
+        MapIter { map: Some(self) }
+    }
+}
+pub struct MapIter {
+    map: Option<Map>,
+}
+impl Iterator for MapIter {
+    type Item = Map;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.map.as_ref()?;
+        let cur = self.map.take();
+        let new = cur.clone().map(Map::step);
+        (new != cur).then(|| self.map = new);
+        cur
+    }
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.tiles.fmt(f)
+    }
+}
+
+impl Render for Map {
+    fn render(&self) -> Frame {
+        self.tiles.map(|tile| Pixel {
+            glyph: tile.to_string().chars().next().unwrap_or('.'),
+            colour: match tile {
+                Tile::Occupied(Cucumber::EastFacing) => [230, 110, 40],
+                Tile::Occupied(Cucumber::SouthFacing) => [40, 140, 230],
+                Tile::Empty => [20, 20, 30],
+            },
+        })
+    }
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Map> {
+    let tile = one_of("v>.").map(|c| match c {
+        '>' => Tile::Occupied(Cucumber::EastFacing),
+        'v' => Tile::Occupied(Cucumber::SouthFacing),
+        _ => Tile::Empty,
+    });
+    let parser = char_grid("sea cucumber row", tile).map(Map::new);
+    Ok(parse_all(input, parser)?)
+}
+
+/// The first step on which no sea cucumber moves.
+pub fn steps_until_stopped(map: &Map) -> anyhow::Result<usize> {
+    let steps = map.clone().iter().take(MAX_STEPS + 1).count();
+    ensure!(
+        steps <= MAX_STEPS,
+        "the sea cucumbers are still moving after {} steps",
+        MAX_STEPS
+    );
+    Ok(steps)
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    type Parsed = Map;
+    type Part1 = usize;
+    type Part2 = &'static str;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input.trim())
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        steps_until_stopped(parsed)
+    }
+    fn part2(_: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        Ok("Merry Christmas!")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const INPUT: &str = r#"
+v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>
+    "#;
+    const INPUT1: &str = r#"
+....>.>v.>
+v.v>.>v.v.
+>v>>..>v..
+>>v>v>.>.v
+.>v.v...v.
+v>>.>vvv..
+..v...>>..
+vv...>>vv.
+>.v.v..v.v
+    "#;
+    const INPUT2: &str = r#"
+>.v.v>>..v
+v.v.>>vv..
+>v>.>.>.v.
+>>v>v.>v>.
+.>..v....v
+.>v>>.v.v.
+v....v>v>.
+.vv..>>v..
+v>.....vv.
+    "#;
+
+    #[test]
+    fn step() -> anyhow::Result<()> {
+        assert_eq!(parse(">...>>...>")?.step_hor(), parse(".>..>.>..>")?);
+        let map = parse(INPUT.trim())?;
+        let map = map.step();
+        assert_eq!(parse(INPUT1.trim())?, map);
+
+        let map = map.step();
+        assert_eq!(parse(INPUT2.trim())?, map);
+
+        Ok(())
+    }
+    #[test]
+    fn part1() -> anyhow::Result<()> {
+        let map = parse(INPUT.trim())?;
+        assert_eq!(steps_until_stopped(&map)?, 58);
+        assert!(steps_until_stopped(&parse(">..")?).is_err());
+        Ok(())
+    }
+}
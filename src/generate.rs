@@ -0,0 +1,651 @@
+//! Synthetic puzzle inputs for stress testing, written in each day's input format.
+//!
+//! Every generator is seeded, so the same day, size and seed always give the same input.
+use crate::{
+    day16::{self, Packet, PacketKind},
+    day17,
+};
+use itertools::Itertools;
+use rand::{
+    seq::{index::sample, SliceRandom},
+    Rng, SeedableRng,
+};
+use rand_chacha::ChaCha8Rng;
+use std::collections::BTreeSet;
+
+type Random = ChaCha8Rng;
+
+#[derive(Clone, Copy)]
+pub struct Generator {
+    pub day: u8,
+    /// What the size parameter counts for this day.
+    pub size: &'static str,
+    /// About the size of a real puzzle input.
+    pub default_size: usize,
+    generate: fn(&mut Random, usize) -> String,
+}
+
+impl Generator {
+    pub fn generate(&self, size: usize, seed: u64) -> String {
+        let mut input = (self.generate)(&mut Random::seed_from_u64(seed), size.max(1));
+        input.push('\n');
+        input
+    }
+}
+
+pub const GENERATORS: [Generator; 25] = [
+    Generator {
+        day: 1,
+        size: "depth measurements",
+        default_size: 2000,
+        generate: sonar_sweep,
+    },
+    Generator {
+        day: 2,
+        size: "commands",
+        default_size: 1000,
+        generate: dive,
+    },
+    Generator {
+        day: 3,
+        size: "report lines",
+        default_size: 1000,
+        generate: binary_diagnostic,
+    },
+    Generator {
+        day: 4,
+        size: "boards",
+        default_size: 100,
+        generate: bingo,
+    },
+    Generator {
+        day: 5,
+        size: "vent lines",
+        default_size: 500,
+        generate: hydrothermal_venture,
+    },
+    Generator {
+        day: 6,
+        size: "fish",
+        default_size: 300,
+        generate: lanternfish,
+    },
+    Generator {
+        day: 7,
+        size: "crabs",
+        default_size: 1000,
+        generate: crabs,
+    },
+    Generator {
+        day: 8,
+        size: "note entries",
+        default_size: 200,
+        generate: seven_segment_search,
+    },
+    Generator {
+        day: 9,
+        size: "map side",
+        default_size: 100,
+        generate: |rng, size| digit_map(rng, size, 0..10),
+    },
+    Generator {
+        day: 10,
+        size: "navigation lines",
+        default_size: 100,
+        generate: syntax_scoring,
+    },
+    Generator {
+        day: 11,
+        size: "grid side",
+        default_size: 10,
+        generate: |rng, size| digit_map(rng, size, 0..10),
+    },
+    Generator {
+        day: 12,
+        size: "small caves",
+        default_size: 8,
+        generate: passage_pathing,
+    },
+    Generator {
+        day: 13,
+        size: "dots",
+        default_size: 800,
+        generate: transparent_origami,
+    },
+    Generator {
+        day: 14,
+        size: "template length",
+        default_size: 20,
+        generate: extended_polymerization,
+    },
+    Generator {
+        day: 15,
+        size: "map side",
+        default_size: 100,
+        generate: |rng, size| digit_map(rng, size, 1..10),
+    },
+    Generator {
+        day: 16,
+        size: "nesting depth",
+        default_size: 64,
+        generate: packet_decoder,
+    },
+    Generator {
+        day: 17,
+        size: "distance to the target",
+        default_size: 100,
+        generate: trick_shot,
+    },
+    Generator {
+        day: 18,
+        size: "snailfish numbers",
+        default_size: 100,
+        generate: snailfish,
+    },
+    Generator {
+        day: 19,
+        size: "scanners",
+        default_size: 30,
+        generate: beacon_scanner,
+    },
+    Generator {
+        day: 20,
+        size: "image side",
+        default_size: 100,
+        generate: trench_map,
+    },
+    Generator {
+        day: 21,
+        size: "unused",
+        default_size: 2,
+        generate: dirac_dice,
+    },
+    Generator {
+        day: 22,
+        size: "reboot steps",
+        default_size: 420,
+        generate: reactor_reboot,
+    },
+    Generator {
+        day: 23,
+        size: "unused",
+        default_size: 2,
+        generate: amphipod,
+    },
+    Generator {
+        day: 24,
+        size: "model number digits, rounded down to an even number up to 18",
+        default_size: 14,
+        generate: alu,
+    },
+    Generator {
+        day: 25,
+        size: "map side",
+        default_size: 137,
+        generate: sea_cucumber,
+    },
+];
+
+pub fn generator(day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|g| g.day == day)
+}
+
+fn sonar_sweep(rng: &mut Random, size: usize) -> String {
+    let mut depth: u32 = rng.gen_range(100..200);
+    (0..size)
+        .map(|_| {
+            depth = depth.saturating_add_signed(rng.gen_range(-20..=30));
+            depth
+        })
+        .join("\n")
+}
+
+/// Never goes up past the surface, in either part's model.
+fn dive(rng: &mut Random, size: usize) -> String {
+    let mut depth = 0;
+    (0..size)
+        .map(|_| {
+            let units = rng.gen_range(1..10);
+            match rng.gen_range(0..3) {
+                0 => format!("forward {}", units),
+                1 if units <= depth => {
+                    depth -= units;
+                    format!("up {}", units)
+                }
+                _ => {
+                    depth += units;
+                    format!("down {}", units)
+                }
+            }
+        })
+        .join("\n")
+}
+
+/// Distinct numbers, so both ratings narrow down to a single one.
+fn binary_diagnostic(rng: &mut Random, size: usize) -> String {
+    let width = (usize::BITS - (2 * size).leading_zeros()).max(12) as usize;
+    sample(rng, 1 << width, size)
+        .iter()
+        .map(|n| format!("{:0width$b}", n, width = width))
+        .join("\n")
+}
+
+/// Every number gets drawn, so every board wins.
+fn bingo(rng: &mut Random, size: usize) -> String {
+    let max = (size * 25).max(100);
+    let mut draw = (0..max).collect_vec();
+    draw.shuffle(rng);
+    let boards = (0..size)
+        .map(|_| {
+            sample(rng, max, 25)
+                .iter()
+                .chunks(5)
+                .into_iter()
+                .map(|row| row.map(|n| format!("{:>2}", n)).join(" "))
+                .join("\n")
+        })
+        .join("\n\n");
+    format!("{}\n\n{}", draw.iter().join(","), boards)
+}
+
+/// Horizontal, vertical and 45 degree lines on a 1000x1000 floor.
+fn hydrothermal_venture(rng: &mut Random, size: usize) -> String {
+    const SIDE: i32 = 1000;
+    (0..size)
+        .map(|_| {
+            let (x1, y1) = (rng.gen_range(0..SIDE), rng.gen_range(0..SIDE));
+            let (x2, y2) = match rng.gen_range(0..3) {
+                0 => (rng.gen_range(0..SIDE), y1),
+                1 => (x1, rng.gen_range(0..SIDE)),
+                _ => {
+                    let mut direction = || if rng.gen() { 1 } else { -1 };
+                    let (dx, dy) = (direction(), direction());
+                    let room = |p: i32, d: i32| if d > 0 { SIDE - 1 - p } else { p };
+                    let len = rng.gen_range(0..=room(x1, dx).min(room(y1, dy)));
+                    (x1 + dx * len, y1 + dy * len)
+                }
+            };
+            format!("{},{} -> {},{}", x1, y1, x2, y2)
+        })
+        .join("\n")
+}
+
+fn lanternfish(rng: &mut Random, size: usize) -> String {
+    (0..size).map(|_| rng.gen_range(1..=5)).join(",")
+}
+
+fn crabs(rng: &mut Random, size: usize) -> String {
+    let max = (2 * size).max(10);
+    (0..size).map(|_| rng.gen_range(0..max)).join(",")
+}
+
+fn seven_segment_search(rng: &mut Random, size: usize) -> String {
+    const DIGITS: [&str; 10] = [
+        "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+    ];
+    (0..size)
+        .map(|_| {
+            let shown = (0..4).map(|_| rng.gen_range(0..10)).collect_vec();
+            let mut wiring = ('a'..='g').collect_vec();
+            wiring.shuffle(rng);
+            let mut scramble = |digit: &str| {
+                let mut segments = digit
+                    .bytes()
+                    .map(|s| wiring[(s - b'a') as usize])
+                    .collect_vec();
+                segments.shuffle(rng);
+                segments.into_iter().collect::<String>()
+            };
+            let mut patterns = DIGITS.iter().map(|d| scramble(d)).collect_vec();
+            let output = shown.iter().map(|&d| scramble(DIGITS[d])).collect_vec();
+            patterns.shuffle(rng);
+            format!("{} | {}", patterns.join(" "), output.join(" "))
+        })
+        .join("\n")
+}
+
+fn digit_map(rng: &mut Random, size: usize, digits: std::ops::Range<u8>) -> String {
+    (0..size)
+        .map(|_| (0..size).map(|_| rng.gen_range(digits.clone())).join(""))
+        .join("\n")
+}
+
+/// Half the lines are corrupted; the rest are incomplete, and never by more than a dozen
+/// brackets so completion scores fit in a `u64`.
+fn syntax_scoring(rng: &mut Random, size: usize) -> String {
+    const PAIRS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+    (0..size)
+        .map(|_| {
+            let (mut line, mut open) = (String::new(), vec![]);
+            for _ in 0..rng.gen_range(40..110) {
+                if !open.is_empty() && (open.len() >= 12 || rng.gen_bool(0.45)) {
+                    line.extend(open.pop());
+                } else {
+                    let (o, c) = PAIRS[rng.gen_range(0..4)];
+                    line.push(o);
+                    open.push(c);
+                }
+            }
+            if open.is_empty() {
+                let (o, c) = PAIRS[rng.gen_range(0..4)];
+                line.push(o);
+                open.push(c);
+            }
+            if rng.gen_bool(0.5) {
+                let expected = open[open.len() - 1];
+                let wrong = PAIRS.iter().map(|&(_, c)| c).filter(|&c| c != expected);
+                line.push(wrong.collect_vec()[rng.gen_range(0..3)]);
+            }
+            line
+        })
+        .join("\n")
+}
+
+/// Big caves are never connected to each other, which would allow endless paths.
+fn passage_pathing(rng: &mut Random, size: usize) -> String {
+    fn name(mut i: usize, upper: bool) -> String {
+        let base = if upper { b'A' } else { b'a' };
+        let mut name = vec![];
+        loop {
+            name.push((base + (i % 26) as u8) as char);
+            i /= 26;
+            if name.len() >= 2 && i == 0 {
+                break;
+            }
+        }
+        name.into_iter().collect()
+    }
+    let small = (0..)
+        .map(|i| name(i, false))
+        .filter(|n| n != "end")
+        .take(size)
+        .collect_vec();
+    let big = (0..(size / 3).max(1)).map(|i| name(i, true)).collect_vec();
+    let caves = small.iter().chain(&big).collect_vec();
+    let mut edges = BTreeSet::new();
+    for end in ["start", "end"] {
+        for cave in caves.choose_multiple(rng, 2) {
+            edges.insert((end.to_owned(), cave.to_string()));
+        }
+    }
+    for cave in &small {
+        for other in caves.choose_multiple(rng, 2) {
+            if cave != *other {
+                edges.insert((cave.to_string(), other.to_string()));
+            }
+        }
+    }
+    edges.iter().map(|(a, b)| format!("{}-{}", a, b)).join("\n")
+}
+
+/// Ten folds, each exactly through the middle of the paper, down to a 39x5 code.
+fn transparent_origami(rng: &mut Random, size: usize) -> String {
+    let (mut width, mut height) = (39, 5);
+    let mut folds = vec![];
+    for _ in 0..5 {
+        folds.push(format!("fold along y={}", height));
+        folds.push(format!("fold along x={}", width));
+        width = 2 * width + 1;
+        height = 2 * height + 1;
+    }
+    folds.reverse();
+    // the paper is as big as the furthest dots
+    let mut dots = vec![(width - 1, 0), (0, height - 1)];
+    dots.extend((0..size).map(|_| (rng.gen_range(0..width), rng.gen_range(0..height))));
+    let dots = dots.iter().map(|(x, y)| format!("{},{}", x, y)).join("\n");
+    format!("{}\n\n{}", dots, folds.join("\n"))
+}
+
+/// One insertion rule for every pair of ten elements.
+fn extended_polymerization(rng: &mut Random, size: usize) -> String {
+    const ELEMENTS: &[u8] = b"BCFHKNOPSV";
+    let mut element = || ELEMENTS[rng.gen_range(0..ELEMENTS.len())] as char;
+    let template = (0..size.max(2)).map(|_| element()).collect::<String>();
+    let rules = ELEMENTS
+        .iter()
+        .cartesian_product(ELEMENTS)
+        .map(|(&a, &b)| format!("{}{} -> {}", a as char, b as char, element()))
+        .join("\n");
+    format!("{}\n\n{}", template, rules)
+}
+
+/// A spine of nested operators, as deep as the parser allows, with literal operands along it.
+fn packet_decoder(rng: &mut Random, size: usize) -> String {
+    let depth = size.clamp(1, day16::MAX_DEPTH);
+    let literal = |rng: &mut Random| Packet::literal(rng.gen(), rng.gen_range(0..16));
+    let mut packet = literal(rng);
+    for _ in 0..depth {
+        let version = rng.gen();
+        packet = match rng.gen_range(0..6) {
+            0 => Packet::operator(version, PacketKind::Sum, vec![packet, literal(rng)]),
+            1 => Packet::operator(version, PacketKind::Min, vec![literal(rng), packet]),
+            2 => Packet::operator(version, PacketKind::Max, vec![packet]),
+            3 => Packet::operator(version, PacketKind::Gt, vec![packet, literal(rng)]),
+            4 => Packet::operator(version, PacketKind::Lt, vec![literal(rng), packet]),
+            _ => Packet::operator(version, PacketKind::Eq, vec![packet, literal(rng)]),
+        };
+    }
+    packet.to_hex()
+}
+
+fn trick_shot(rng: &mut Random, size: usize) -> String {
+    let size = size.clamp(10, day17::MAX_COORD as usize * 3 / 4) as i32;
+    let x1 = rng.gen_range(size / 2..size);
+    let x2 = x1 + rng.gen_range(5..=size / 4 + 5);
+    let y2 = -rng.gen_range(size / 2..size);
+    let y1 = y2 - rng.gen_range(5..=size / 4 + 5);
+    format!("target area: x={}..{}, y={}..{}", x1, x2, y1, y2)
+}
+
+/// Reduced numbers, never nested more than four pairs deep.
+fn snailfish(rng: &mut Random, size: usize) -> String {
+    fn pair(rng: &mut Random, depth: usize) -> String {
+        let element = |rng: &mut Random| {
+            if depth < 4 && rng.gen_bool(0.6) {
+                pair(rng, depth + 1)
+            } else {
+                rng.gen_range(0..10).to_string()
+            }
+        };
+        let left = element(rng);
+        format!("[{},{}]", left, element(rng))
+    }
+    (0..size).map(|_| pair(rng, 1)).join("\n")
+}
+
+/// Scanners along a random walk, each overlapping the previous one by at least 12 beacons.
+fn beacon_scanner(rng: &mut Random, size: usize) -> String {
+    const RANGE: i32 = 1000;
+    type Vec3 = [i32; 3];
+    fn within(rng: &mut Random, lo: Vec3, hi: Vec3) -> Vec3 {
+        [0, 1, 2].map(|a| rng.gen_range(lo[a]..=hi[a]))
+    }
+    fn seen(lo: &Vec3, hi: &Vec3, b: &Vec3) -> bool {
+        (0..3).all(|a| (lo[a]..=hi[a]).contains(&b[a]))
+    }
+    let mut scanners: Vec<Vec3> = vec![[0; 3]];
+    let mut beacons = BTreeSet::new();
+    for _ in 1..size {
+        let last = scanners[scanners.len() - 1];
+        let next = [0, 1, 2].map(|a| last[a] + rng.gen_range(-RANGE / 2..=RANGE / 2));
+        let lo = [0, 1, 2].map(|a| last[a].max(next[a]) - RANGE);
+        let hi = [0, 1, 2].map(|a| last[a].min(next[a]) + RANGE);
+        while beacons.iter().filter(|b| seen(&lo, &hi, b)).count() < 12 {
+            beacons.insert(within(rng, lo, hi));
+        }
+        scanners.push(next);
+    }
+    for scanner in &scanners {
+        let lo = scanner.map(|c| c - RANGE);
+        let hi = scanner.map(|c| c + RANGE);
+        for _ in 0..rng.gen_range(5..15) {
+            beacons.insert(within(rng, lo, hi));
+        }
+    }
+    // the 24 rotations: axis permutations with signs that keep the determinant at 1
+    let rotations = [0, 1, 2]
+        .into_iter()
+        .permutations(3)
+        .cartesian_product(0..8u32)
+        .filter(|(axes, signs)| {
+            let odd = [[0, 2, 1], [1, 0, 2], [2, 1, 0]].contains(&[axes[0], axes[1], axes[2]]);
+            odd == (signs.count_ones() % 2 == 1)
+        })
+        .collect_vec();
+    scanners
+        .iter()
+        .enumerate()
+        .map(|(i, scanner)| {
+            let (axes, signs) = &rotations[if i == 0 { 0 } else { rng.gen_range(0..24) }];
+            let lo = scanner.map(|c| c - RANGE);
+            let hi = scanner.map(|c| c + RANGE);
+            let mut report = beacons
+                .iter()
+                .filter(|b| seen(&lo, &hi, b))
+                .map(|b| {
+                    let rel = [0, 1, 2].map(|a| b[a] - scanner[a]);
+                    [0, 1, 2].map(|a| rel[axes[a]] * if signs >> a & 1 == 1 { -1 } else { 1 })
+                })
+                .collect_vec();
+            report.shuffle(rng);
+            let report = report.iter().map(|b| b.iter().join(",")).join("\n");
+            format!("--- scanner {} ---\n{}", i, report)
+        })
+        .join("\n\n")
+}
+
+/// Never lights up the whole infinite image at once.
+fn trench_map(rng: &mut Random, size: usize) -> String {
+    let mut algorithm = (0..512).map(|_| rng.gen_bool(0.5)).collect_vec();
+    if algorithm[0] {
+        algorithm[511] = false;
+    }
+    let pixel = |lit: bool| if lit { '#' } else { '.' };
+    let image = (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| pixel(rng.gen_bool(0.5)))
+                .collect::<String>()
+        })
+        .join("\n");
+    let algorithm = algorithm.into_iter().map(pixel).collect::<String>();
+    format!("{}\n\n{}", algorithm, image)
+}
+
+fn dirac_dice(rng: &mut Random, _size: usize) -> String {
+    (1..=2)
+        .map(|player| {
+            format!(
+                "Player {} starting position: {}",
+                player,
+                rng.gen_range(1..=10)
+            )
+        })
+        .join("\n")
+}
+
+/// A handful of steps inside the initialization region, then steps across the whole reactor.
+fn reactor_reboot(rng: &mut Random, size: usize) -> String {
+    (0..size)
+        .map(|i| {
+            let (extent, max_len) = if i < 20 { (50, 50) } else { (100_000, 30_000) };
+            let mut range = || {
+                let lo = rng.gen_range(-extent..extent);
+                let hi = (lo + rng.gen_range(0..max_len)).min(extent);
+                format!("{}..{}", lo, hi)
+            };
+            let (x, y, z) = (range(), range(), range());
+            let on = if i == 0 || rng.gen_bool(0.7) {
+                "on"
+            } else {
+                "off"
+            };
+            format!("{} x={},y={},z={}", on, x, y, z)
+        })
+        .join("\n")
+}
+
+fn amphipod(rng: &mut Random, _size: usize) -> String {
+    let mut pods = *b"AABBCCDD";
+    pods.shuffle(rng);
+    let row = |pods: &[u8]| pods.iter().map(|&p| p as char).join("#");
+    format!(
+        "#############\n#...........#\n###{}###\n  #{}#\n  #########",
+        row(&pods[..4]),
+        row(&pods[4..])
+    )
+}
+
+/// A MONAD program: one block per digit, each pushing a digit on `z` as a base 26 stack or
+/// popping one, and the pops only succeed if the digits match up.
+fn alu(rng: &mut Random, size: usize) -> String {
+    let pairs = (size / 2).clamp(1, 9);
+    // a random balanced sequence of pushes (true) and pops (false)
+    let (mut pushes, mut open, mut blocks) = (pairs, 0, vec![]);
+    while pushes > 0 || open > 0 {
+        let push = pushes > 0 && (open == 0 || rng.gen_bool(0.5));
+        if push {
+            pushes -= 1;
+            open += 1;
+        } else {
+            open -= 1;
+        }
+        blocks.push(push);
+    }
+    let mut stack = vec![];
+    blocks
+        .into_iter()
+        .map(|push| {
+            let (div, check, offset) = if push {
+                let offset = rng.gen_range(1..=16);
+                stack.push(offset);
+                (1, rng.gen_range(10..=16), offset)
+            } else {
+                let pushed = stack.pop().unwrap_or_default();
+                let delta: i32 = rng.gen_range(-8..=8);
+                (26, delta - pushed, rng.gen_range(1..=16))
+            };
+            format!(
+                "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {}\nadd x {}\neql x w\neql x 0\n\
+                 mul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\nadd y {}\n\
+                 mul y x\nadd z y",
+                div, check, offset
+            )
+        })
+        .join("\n")
+}
+
+fn sea_cucumber(rng: &mut Random, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| ['.', '>', 'v'][rng.gen_range(0..3)])
+                .collect::<String>()
+        })
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DAYS;
+
+    #[test]
+    fn parses() {
+        for (day, generator) in DAYS.iter().zip(&GENERATORS) {
+            assert_eq!(day.day, generator.day);
+            for seed in 0..3 {
+                let input = generator.generate(generator.default_size.min(20), seed);
+                if let Err(e) = (day.parse)(&input) {
+                    panic!("day {} seed {}: {:#}\n{}", day.day, seed, e, input);
+                }
+            }
+        }
+    }
+    #[test]
+    fn reproducible() {
+        let day19 = generator(19).unwrap();
+        assert_eq!(day19.generate(5, 7), day19.generate(5, 7));
+        assert_ne!(day19.generate(5, 7), day19.generate(5, 8));
+    }
+}
@@ -0,0 +1,228 @@
+use crate::parsing::{line, IResult};
+use nom::{
+    character::complete::line_ending,
+    error::{ErrorKind, VerboseError, VerboseErrorKind},
+    multi::many1,
+    Parser,
+};
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+/// `(x, y)`: column, then row, both from the top left corner.
+pub type Pos = (usize, usize);
+
+const NEIGHBOURS4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const NEIGHBOURS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Dense row-major 2D grid, optionally wrapping around its edges like a torus.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+    wrapping: bool,
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+            wrapping: false,
+        }
+    }
+    /// Makes neighbours and offsets wrap around the edges.
+    pub fn wrapping(mut self, wrapping: bool) -> Self {
+        self.wrapping = wrapping;
+        self
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    /// Row-major index of `pos`, stable for the lifetime of the grid.
+    pub fn index_of(&self, (x, y): Pos) -> usize {
+        y * self.width + x
+    }
+    pub fn get(&self, (x, y): Pos) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[self.index_of((x, y))])
+    }
+    pub fn get_mut(&mut self, (x, y): Pos) -> Option<&mut T> {
+        let idx = self.index_of((x, y));
+        (x < self.width && y < self.height).then(|| &mut self.cells[idx])
+    }
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+            wrapping: self.wrapping,
+        }
+    }
+    /// `pos` moved by `(dx, dy)`, or `None` past the edge of a grid that doesn't wrap.
+    pub fn offset(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let (w, h) = (self.width as isize, self.height as isize);
+        let (x, y) = (x as isize + dx, y as isize + dy);
+        if self.wrapping {
+            Some((x.rem_euclid(w) as usize, y.rem_euclid(h) as usize))
+        } else {
+            ((0..w).contains(&x) && (0..h).contains(&y)).then_some((x as usize, y as usize))
+        }
+    }
+    /// Orthogonal neighbours of `pos`.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS4.iter().filter_map(move |&d| self.offset(pos, d))
+    }
+    /// Orthogonal and diagonal neighbours of `pos`.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS8.iter().filter_map(move |&d| self.offset(pos, d))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos).expect("position inside the grid")
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        self.get_mut(pos).expect("position inside the grid")
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a character map, one `cell` per character and one row labelled `label` per line.
+///
+/// Every row must be as wide as the first one.
+pub fn char_grid<'i, T, P>(
+    label: &'static str,
+    mut cell: P,
+) -> impl FnMut(&'i str) -> IResult<'i, Grid<T>>
+where
+    P: Parser<&'i str, T, VerboseError<&'i str>>,
+{
+    move |input: &'i str| {
+        let mut row = line(label, many1(|i| cell.parse(i)));
+        let (mut rest, mut cells) = row(input)?;
+        let width = cells.len();
+        let mut height = 1;
+        while let Ok((next, _)) = line_ending::<_, VerboseError<&str>>(rest) {
+            let Ok((after, mut cols)) = row(next) else {
+                break;
+            };
+            if cols.len() != width {
+                let at = next
+                    .char_indices()
+                    .nth(width.min(cols.len()))
+                    .map_or(next, |(i, _)| &next[i..]);
+                return Err(nom::Err::Failure(VerboseError {
+                    errors: vec![
+                        (at, VerboseErrorKind::Nom(ErrorKind::Count)),
+                        (next, VerboseErrorKind::Context(label)),
+                    ],
+                }));
+            }
+            cells.append(&mut cols);
+            height += 1;
+            rest = after;
+        }
+        let grid = Grid {
+            width,
+            height,
+            cells,
+            wrapping: false,
+        };
+        Ok((rest, grid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::parse_all;
+    use nom::character::complete::one_of;
+    use proptest::prelude::*;
+
+    fn parse(input: &str) -> Result<Grid<char>, crate::parsing::ParseError> {
+        parse_all(input, char_grid("row", one_of(".#")))
+    }
+
+    #[test]
+    fn neighbours() -> anyhow::Result<()> {
+        let grid = parse("#..\n.#.\n..#\n")?;
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid[(1, 1)], '#');
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8((2, 2)).count(), 3);
+
+        let grid = grid.wrapping(true);
+        assert_eq!(grid.offset((0, 0), (-1, -1)), Some((2, 2)));
+        assert_eq!(grid.neighbours4((0, 0)).count(), 4);
+        assert_eq!(grid.to_string(), "#..\n.#.\n..#\n");
+        Ok(())
+    }
+    proptest! {
+        #[test]
+        fn round_trip(rows in (1..20usize).prop_flat_map(|w| {
+            prop::collection::vec(prop::collection::vec(prop::sample::select(vec!['.', '#']), w), 1..20)
+        })) {
+            let grid = Grid::from_fn(rows[0].len(), rows.len(), |(x, y)| rows[y][x]);
+            prop_assert_eq!(parse(&grid.to_string()), Ok(grid));
+        }
+    }
+    #[test]
+    fn ragged() {
+        let err = parse("#..\n.#\n..#").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.expected, "wrong number of cells");
+    }
+}
@@ -0,0 +1,297 @@
+use anyhow::Context;
+use std::{
+    fmt::Display,
+    fs::read_to_string,
+    io::{self, Read},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+pub mod answers;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod generate;
+pub mod grid;
+pub mod output;
+pub mod parsing;
+pub mod profile;
+pub mod render;
+
+/// A single day's puzzle: how to parse its input and how to answer both parts.
+pub trait Solution {
+    const DAY: u8;
+    type Parsed: 'static;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed>;
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1>;
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2>;
+}
+
+/// Parsed input of some day with its `Solution` type erased.
+pub trait Puzzle {
+    fn part1(&self) -> anyhow::Result<String>;
+    fn part2(&self) -> anyhow::Result<String>;
+}
+
+struct Erased<S: Solution>(S::Parsed);
+
+impl<S: Solution> Puzzle for Erased<S> {
+    fn part1(&self) -> anyhow::Result<String> {
+        S::part1(&self.0).map(|answer| answer.to_string())
+    }
+    fn part2(&self) -> anyhow::Result<String> {
+        S::part2(&self.0).map(|answer| answer.to_string())
+    }
+}
+
+fn parse_erased<S: Solution + 'static>(input: &str) -> anyhow::Result<Box<dyn Puzzle>> {
+    let parsed = S::parse(input)?;
+    Ok(Box::new(Erased::<S>(parsed)))
+}
+
+/// Environment variable naming a directory laid out like the crate root, with `day-NN/input.txt` in it.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where a day reads its puzzle input from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// `day-NN/input.txt` under `$AOC_INPUT_DIR`, or under the crate root if it is unset
+    #[default]
+    Default,
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// `-` reads stdin, any other argument is a file path.
+    pub fn from_arg(arg: Option<PathBuf>) -> Self {
+        match arg {
+            None => Self::Default,
+            Some(path) if path.as_os_str() == "-" => Self::Stdin,
+            Some(path) => Self::File(path),
+        }
+    }
+    pub fn from_args() -> Self {
+        Self::from_arg(std::env::args_os().nth(1).map(PathBuf::from))
+    }
+    /// `$AOC_INPUT_DIR`, or the crate root if it is unset.
+    pub fn input_dir() -> PathBuf {
+        std::env::var_os(INPUT_DIR_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")))
+    }
+    pub fn default_path(day: u8) -> PathBuf {
+        Self::input_dir()
+            .join(format!("day-{:02}", day))
+            .join("input.txt")
+    }
+    pub fn read(&self, day: u8) -> anyhow::Result<String> {
+        match self {
+            Self::Default => {
+                let path = Self::default_path(day);
+                read_to_string(&path).with_context(|| {
+                    format!(
+                        "no input for day {:02} at {}; pass an input file, `-` for stdin or set {}",
+                        day,
+                        path.display(),
+                        INPUT_DIR_VAR
+                    )
+                })
+            }
+            Self::File(path) => read_to_string(path)
+                .with_context(|| format!("failed to read input file {}", path.display())),
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .context("failed to read input from stdin")?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+/// One answered part of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    /// Time spent parsing the input, shared by both parts
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub day: u8,
+    pub parse: fn(&str) -> anyhow::Result<Box<dyn Puzzle>>,
+}
+
+impl Day {
+    pub const fn of<S: Solution + 'static>() -> Self {
+        Self {
+            day: S::DAY,
+            parse: parse_erased::<S>,
+        }
+    }
+    /// Parses `input` once, then answers the selected parts, handing each `Record` to `report`
+    /// as soon as it is ready.
+    pub fn solve(
+        &self,
+        input: &str,
+        part: Option<u8>,
+        mut report: impl FnMut(Record),
+    ) -> anyhow::Result<()> {
+        let start = Instant::now();
+        let puzzle = (self.parse)(input)?;
+        let parse_time = start.elapsed();
+        for p in [1, 2]
+            .into_iter()
+            .filter(|p| part.is_none_or(|part| part == *p))
+        {
+            let start = Instant::now();
+            let answer = if p == 1 {
+                puzzle.part1()
+            } else {
+                puzzle.part2()
+            }?;
+            report(Record {
+                day: self.day,
+                part: p,
+                answer,
+                parse_time,
+                solve_time: start.elapsed(),
+            });
+        }
+        Ok(())
+    }
+    pub fn run(&self, source: &InputSource, part: Option<u8>) -> anyhow::Result<()> {
+        let input = source.read(self.day)?;
+        self.solve(&input, part, |record| {
+            println!("part{} result is {}", record.part, record.answer)
+        })
+    }
+}
+
+pub const DAYS: [Day; 25] = [
+    Day::of::<day01::Day01>(),
+    Day::of::<day02::Day02>(),
+    Day::of::<day03::Day03>(),
+    Day::of::<day04::Day04>(),
+    Day::of::<day05::Day05>(),
+    Day::of::<day06::Day06>(),
+    Day::of::<day07::Day07>(),
+    Day::of::<day08::Day08>(),
+    Day::of::<day09::Day09>(),
+    Day::of::<day10::Day10>(),
+    Day::of::<day11::Day11>(),
+    Day::of::<day12::Day12>(),
+    Day::of::<day13::Day13>(),
+    Day::of::<day14::Day14>(),
+    Day::of::<day15::Day15>(),
+    Day::of::<day16::Day16>(),
+    Day::of::<day17::Day17>(),
+    Day::of::<day18::Day18>(),
+    Day::of::<day19::Day19>(),
+    Day::of::<day20::Day20>(),
+    Day::of::<day21::Day21>(),
+    Day::of::<day22::Day22>(),
+    Day::of::<day23::Day23>(),
+    Day::of::<day24::Day24>(),
+    Day::of::<day25::Day25>(),
+];
+
+pub fn day(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry() {
+        assert!(DAYS.iter().map(|d| d.day).eq(1..=25));
+        assert_eq!(day(12).map(|d| d.day), Some(12));
+        assert!(day(26).is_none());
+    }
+    #[test]
+    fn input_source() {
+        assert_eq!(InputSource::from_arg(None), InputSource::Default);
+        assert_eq!(InputSource::from_arg(Some("-".into())), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg(Some("day-01/input.txt".into())),
+            InputSource::File("day-01/input.txt".into())
+        );
+        let missing = InputSource::File("no/such/input.txt".into()).read(1);
+        assert!(missing.is_err());
+    }
+    /// Random edits to every bundled example have to give an error rather than a panic, both
+    /// when parsing and when solving whatever still parses.
+    #[test]
+    fn hostile_input() {
+        use proptest::{
+            prelude::*,
+            test_runner::{Config, TestRunner},
+        };
+        let noise = prop::sample::select(b"0123456789-,=.#>v[]ABCDxyz \n".to_vec());
+        for day in &DAYS {
+            let mut runner = TestRunner::new(Config::with_cases(64));
+            let path = format!(
+                "{}/benches/inputs/day-{:02}.txt",
+                env!("CARGO_MANIFEST_DIR"),
+                day.day
+            );
+            let example = std::fs::read_to_string(path).expect("bundled example");
+            let edit = (0..=example.len(), 0..3u8, noise.clone());
+            runner
+                .run(&prop::collection::vec(edit, 1..8), |edits| {
+                    let mut input = example.clone().into_bytes();
+                    for (at, op, byte) in edits {
+                        let at = at.min(input.len());
+                        match op {
+                            0 if at < input.len() => drop(input.remove(at)),
+                            1 if at < input.len() => input[at] = byte,
+                            _ => input.insert(at, byte),
+                        }
+                    }
+                    if let Ok(puzzle) = (day.parse)(&String::from_utf8_lossy(&input)) {
+                        let _ = puzzle.part1();
+                        // searching the unfolded burrow takes minutes in a debug build, even for
+                        // the example; part 1 searches the same way on the folded one
+                        if day.day != 23 {
+                            let _ = puzzle.part2();
+                        }
+                    }
+                    Ok(())
+                })
+                .unwrap_or_else(|e| panic!("day {}: {}", day.day, e));
+        }
+    }
+}
@@ -1,3 +1,282 @@
-fn main() {
-    println!("Hello, world!");
+use anyhow::Context;
+use aoc_2021::{
+    answers::{Answers, Verdict},
+    generate::generator,
+    output::{write_csv, write_json, Format},
+    profile::{self, CountingAlloc},
+    render::{self, RenderFormat},
+    Day, InputSource, Record, DAYS,
+};
+use clap::{Parser, Subcommand};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::{fs, io, path::PathBuf, time::Duration};
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2021 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a single day or the whole year
+    Run {
+        /// Day to run
+        #[arg(
+            value_parser = clap::value_parser!(u8).range(1..=25),
+            required_unless_present = "all"
+        )]
+        day: Option<u8>,
+        /// Input file, `-` for stdin [default: day-NN/input.txt under $AOC_INPUT_DIR or the crate root]
+        #[arg(conflicts_with = "all")]
+        input: Option<PathBuf>,
+        /// Run every day
+        #[arg(long, conflicts_with = "day")]
+        all: bool,
+        /// Run only this part of the puzzle
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+        /// Worker threads for running days side by side and for the days' parallel loops; 0 uses
+        /// every core. Anything but 1 needs the `parallel` feature
+        #[arg(long, short, default_value_t = 1)]
+        jobs: usize,
+        /// Measure wall time, peak heap and allocations of each parse and part instead of
+        /// printing answers, then print them slowest first
+        #[arg(long, conflicts_with = "format")]
+        profile: bool,
+    },
+    /// Run every day on its default input and compare against the known answers
+    Verify {
+        /// Answers file [default: answers.csv under $AOC_INPUT_DIR or the crate root]
+        #[arg(long)]
+        answers: Option<PathBuf>,
+    },
+    /// Write a synthetic, well-formed input for a day, for stress testing
+    Generate {
+        /// Day to generate an input for
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// How big the input is; what it counts depends on the day [default: about the size of a real input]
+        #[arg(long)]
+        size: Option<usize>,
+        /// Random seed; the same seed always gives the same input
+        #[arg(long, default_value_t = 2021)]
+        seed: u64,
+        /// Output file [default: stdout]
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Draw a grid based day's simulation as text frames, images or an animated GIF
+    Render {
+        /// Day to draw: 11, 15, 20 or 25
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Input file, `-` for stdin [default: day-NN/input.txt under $AOC_INPUT_DIR or the crate root]
+        input: Option<PathBuf>,
+        /// Draw the setup of this part of the puzzle
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: RenderFormat,
+        /// Output file, or directory for PPM and PNG frames [default: stdout for text formats]
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        /// Image pixels per grid cell
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u64).range(1..=64))]
+        scale: u64,
+        /// Stop after this many steps
+        #[arg(long)]
+        steps: Option<usize>,
+        /// Time between frames, in milliseconds
+        #[arg(long, default_value_t = 100)]
+        delay: u64,
+    },
+}
+
+fn main() -> anyhow::Result<()> {
+    let Cli { command } = Cli::parse();
+    match command {
+        Command::Run {
+            day,
+            input,
+            all,
+            part,
+            format,
+            jobs,
+            profile,
+        } => {
+            // the heap counters are shared by every thread, so phases can't overlap
+            anyhow::ensure!(!profile || jobs == 1, "--profile needs --jobs 1");
+            anyhow::ensure!(
+                jobs == 1 || cfg!(feature = "parallel"),
+                "--jobs needs a build with the `parallel` feature"
+            );
+            #[cfg(feature = "parallel")]
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(jobs)
+                .build_global()?;
+            let source = InputSource::from_arg(input);
+            let days: Vec<&Day> = if all {
+                DAYS.iter().collect()
+            } else {
+                day.and_then(aoc_2021::day).into_iter().collect()
+            };
+            if profile {
+                let mut failed = 0;
+                let mut samples = vec![];
+                for day in days {
+                    eprintln!("profiling day {:02}", day.day);
+                    let result = source
+                        .read(day.day)
+                        .and_then(|input| day.profile(&input, part));
+                    match result {
+                        Ok(mut day_samples) => samples.append(&mut day_samples),
+                        Err(e) => {
+                            eprintln!("day {:02} failed: {:#}", day.day, e);
+                            failed += 1;
+                        }
+                    }
+                }
+                profile::write_table(&mut io::stdout().lock(), &samples)?;
+                anyhow::ensure!(failed == 0, "{} day(s) failed", failed);
+                return Ok(());
+            }
+            // Text output streams as parts finish unless days run side by side; everything else
+            // is printed afterwards in day order, whatever order the days finished in.
+            let stream = format == Format::Text && jobs == 1;
+            let print =
+                |record: &Record| println!("part{} result is {}", record.part, record.answer);
+            let solve = |day: &&Day| {
+                if stream {
+                    println!("day {:02}", day.day);
+                }
+                let mut records = vec![];
+                let result = source.read(day.day).and_then(|input| {
+                    day.solve(&input, part, |record| {
+                        if stream {
+                            print(&record);
+                        }
+                        records.push(record);
+                    })
+                });
+                (day.day, records, result)
+            };
+            #[cfg(feature = "parallel")]
+            let outcomes: Vec<_> = if jobs == 1 {
+                days.iter().map(solve).collect()
+            } else {
+                days.par_iter().map(solve).collect()
+            };
+            #[cfg(not(feature = "parallel"))]
+            let outcomes: Vec<_> = days.iter().map(solve).collect();
+            let mut failed = 0;
+            let mut records = vec![];
+            for (day, mut day_records, result) in outcomes {
+                if format == Format::Text && !stream {
+                    println!("day {:02}", day);
+                    day_records.iter().for_each(print);
+                }
+                if let Err(e) = result {
+                    eprintln!("day {:02} failed: {:#}", day, e);
+                    failed += 1;
+                }
+                records.append(&mut day_records);
+            }
+            match format {
+                Format::Text => {}
+                Format::Json => write_json(&mut io::stdout().lock(), &records)?,
+                Format::Csv => write_csv(&mut io::stdout().lock(), &records)?,
+            }
+            anyhow::ensure!(failed == 0, "{} day(s) failed", failed);
+        }
+        Command::Verify { answers } => {
+            let answers = Answers::load(&answers.unwrap_or_else(Answers::default_path))?;
+            let (mut passed, mut failed, mut missing, mut skipped) = (0, 0, 0, 0);
+            for day in &DAYS {
+                if !InputSource::default_path(day.day).exists() {
+                    println!("day {:02}: no input, skipped", day.day);
+                    skipped += 1;
+                    continue;
+                }
+                let result = InputSource::Default.read(day.day).and_then(|input| {
+                    day.solve(&input, None, |record| {
+                        let verdict = match answers.check(&record) {
+                            Verdict::Pass => {
+                                passed += 1;
+                                "pass".to_owned()
+                            }
+                            Verdict::Fail { expected } => {
+                                failed += 1;
+                                format!("FAIL: expected {}, got {}", expected, record.answer)
+                            }
+                            Verdict::Missing => {
+                                missing += 1;
+                                format!("missing, got {}", record.answer)
+                            }
+                        };
+                        println!("day {:02} part{}: {}", record.day, record.part, verdict);
+                    })
+                });
+                if let Err(e) = result {
+                    eprintln!("day {:02} failed: {:#}", day.day, e);
+                    failed += 1;
+                }
+            }
+            println!(
+                "{} passed, {} failed, {} missing, {} day(s) skipped",
+                passed, failed, missing, skipped
+            );
+            anyhow::ensure!(failed == 0, "verification failed");
+        }
+        Command::Generate {
+            day,
+            size,
+            seed,
+            output,
+        } => {
+            let generator = generator(day).context("no generator for this day")?;
+            let size = size.unwrap_or(generator.default_size);
+            eprintln!(
+                "day {:02}: size {} ({}), seed {}",
+                day, size, generator.size, seed
+            );
+            let input = generator.generate(size, seed);
+            match output {
+                Some(path) => fs::write(&path, input)
+                    .with_context(|| format!("failed to write {}", path.display()))?,
+                None => print!("{}", input),
+            }
+        }
+        Command::Render {
+            day,
+            input,
+            part,
+            format,
+            output,
+            scale,
+            steps,
+            delay,
+        } => {
+            let input = InputSource::from_arg(input).read(day)?;
+            let frames = render::frames(day, &input, part)?;
+            let frames = frames.take(steps.map_or(usize::MAX, |steps| steps + 1));
+            render::export(
+                frames,
+                format,
+                output.as_deref(),
+                scale as usize,
+                Duration::from_millis(delay),
+            )?;
+        }
+    }
+    Ok(())
 }
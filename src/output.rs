@@ -0,0 +1,126 @@
+use crate::Record;
+use std::io::{self, Write};
+
+/// How the runner prints its results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// `part1 result is ...` lines, printed as each part finishes
+    #[default]
+    Text,
+    /// A JSON array with one object per answered part
+    Json,
+    /// A header line, then one row per answered part
+    Csv,
+}
+
+/// Writes `records` as a JSON array; times are in nanoseconds.
+pub fn write_json(out: &mut impl Write, records: &[Record]) -> io::Result<()> {
+    writeln!(out, "[")?;
+    for (i, record) in records.iter().enumerate() {
+        let separator = if i + 1 < records.len() { "," } else { "" };
+        writeln!(
+            out,
+            "  {{\"day\": {}, \"part\": {}, \"answer\": {}, \"parse_ns\": {}, \"solve_ns\": {}}}{}",
+            record.day,
+            record.part,
+            json_string(&record.answer),
+            record.parse_time.as_nanos(),
+            record.solve_time.as_nanos(),
+            separator
+        )?;
+    }
+    writeln!(out, "]")
+}
+
+/// Writes `records` as CSV with a header line; times are in nanoseconds.
+pub fn write_csv(out: &mut impl Write, records: &[Record]) -> io::Result<()> {
+    writeln!(out, "day,part,answer,parse_ns,solve_ns")?;
+    for record in records {
+        writeln!(
+            out,
+            "{},{},{},{},{}",
+            record.day,
+            record.part,
+            csv_field(&record.answer),
+            record.parse_time.as_nanos(),
+            record.solve_time.as_nanos()
+        )?;
+    }
+    Ok(())
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                day: 1,
+                part: 1,
+                answer: "7".to_owned(),
+                parse_time: Duration::from_nanos(1500),
+                solve_time: Duration::from_nanos(300),
+            },
+            Record {
+                day: 13,
+                part: 2,
+                answer: "\n#..#\n\"x\",".to_owned(),
+                parse_time: Duration::from_nanos(10),
+                solve_time: Duration::from_nanos(20),
+            },
+        ]
+    }
+
+    #[test]
+    fn json() -> io::Result<()> {
+        let mut out = Vec::new();
+        write_json(&mut out, &records())?;
+        assert_eq!(
+            String::from_utf8_lossy(&out),
+            r#"[
+  {"day": 1, "part": 1, "answer": "7", "parse_ns": 1500, "solve_ns": 300},
+  {"day": 13, "part": 2, "answer": "\n#..#\n\"x\",", "parse_ns": 10, "solve_ns": 20}
+]
+"#
+        );
+        Ok(())
+    }
+    #[test]
+    fn csv() -> io::Result<()> {
+        let mut out = Vec::new();
+        write_csv(&mut out, &records())?;
+        assert_eq!(
+            String::from_utf8_lossy(&out),
+            "day,part,answer,parse_ns,solve_ns\n1,1,7,1500,300\n13,2,\"\n#..#\n\"\"x\"\",\",10,20\n"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,180 @@
+use nom::{
+    branch::alt,
+    character::complete::{line_ending, multispace0, space0},
+    combinator::{cut, eof, peek},
+    error::{context, ErrorKind, VerboseError, VerboseErrorKind},
+    sequence::{preceded, terminated},
+    Finish, Offset, Parser,
+};
+use std::fmt::{self, Display};
+
+pub type IResult<'i, O> = nom::IResult<&'i str, O, VerboseError<&'i str>>;
+
+/// A parse failure located in the original input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// The whole input line the error points into
+    pub snippet: String,
+    pub expected: String,
+    /// Context labels, outermost first
+    pub context: Vec<&'static str>,
+}
+
+impl ParseError {
+    pub fn new(input: &str, error: VerboseError<&str>) -> Self {
+        let (offset, expected) = error
+            .errors
+            .iter()
+            .find_map(|(at, kind)| match kind {
+                VerboseErrorKind::Context(_) => None,
+                VerboseErrorKind::Char(c) => Some((
+                    input.offset(at),
+                    format!("expected '{}'", c.escape_default()),
+                )),
+                VerboseErrorKind::Nom(kind) => Some((input.offset(at), describe(*kind))),
+            })
+            .unwrap_or((input.len(), "invalid input".to_owned()));
+        let context = error
+            .errors
+            .iter()
+            .rev()
+            .filter_map(|(_, kind)| match kind {
+                VerboseErrorKind::Context(ctx) => Some(*ctx),
+                _ => None,
+            })
+            .collect();
+        Self::at(input, offset, expected, context)
+    }
+    pub fn at(
+        input: &str,
+        offset: usize,
+        expected: impl Into<String>,
+        context: Vec<&'static str>,
+    ) -> Self {
+        let offset = offset.min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let snippet = input[line_start..]
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_owned();
+        Self {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet,
+            expected: expected.into(),
+            context,
+        }
+    }
+}
+
+fn describe(kind: ErrorKind) -> String {
+    match kind {
+        ErrorKind::Digit => "expected a number",
+        ErrorKind::Alpha => "expected a letter",
+        ErrorKind::Char | ErrorKind::OneOf => "unexpected character",
+        ErrorKind::Tag => "unexpected text",
+        ErrorKind::Space | ErrorKind::MultiSpace => "expected whitespace",
+        ErrorKind::CrLf => "expected a line break",
+        ErrorKind::TakeUntil => "missing separator",
+        ErrorKind::Eof => "unexpected input",
+        ErrorKind::Verify => "invalid value",
+        ErrorKind::Count => "wrong number of cells",
+        ErrorKind::TooLarge => "too deeply nested",
+        kind => return format!("invalid input ({})", kind.description()),
+    }
+    .to_owned()
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "parse error at line {}, column {}",
+            self.line, self.column
+        )?;
+        if !self.context.is_empty() {
+            write!(f, " in {}", self.context.join(" > "))?;
+        }
+        let gutter = self.line.to_string().len();
+        writeln!(f, ": {}", self.expected)?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(
+            f,
+            "{:gutter$} | {:>column$}",
+            "",
+            "^",
+            gutter = gutter,
+            column = self.column
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Runs `parser` over the whole `input`, only trailing whitespace may be left over.
+pub fn parse_all<'i, O, P>(input: &'i str, parser: P) -> Result<O, ParseError>
+where
+    P: Parser<&'i str, O, VerboseError<&'i str>>,
+{
+    terminated(parser, multispace0.and(eof))
+        .parse(input)
+        .finish()
+        .map(|(_input, parsed)| parsed)
+        .map_err(|e| ParseError::new(input, e))
+}
+
+/// Succeeds at a line break or the end of input, without consuming either.
+pub fn eol(input: &str) -> IResult<'_, ()> {
+    peek(alt((line_ending, eof))).map(|_| ()).parse(input)
+}
+
+/// `parser` labelled `label`; once it matches, only spaces may follow on its line.
+pub fn line<'i, O, P>(label: &'static str, parser: P) -> impl FnMut(&'i str) -> IResult<'i, O>
+where
+    P: Parser<&'i str, O, VerboseError<&'i str>>,
+{
+    context(label, terminated(parser, cut(preceded(space0, eol))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{
+        character::complete::{char, line_ending, u32},
+        error::context,
+        multi::separated_list1,
+        sequence::separated_pair,
+    };
+
+    fn pairs(input: &str) -> Result<Vec<(u32, u32)>, ParseError> {
+        let pair = context("pair", separated_pair(u32, char(','), u32));
+        parse_all(input, separated_list1(line_ending, pair))
+    }
+
+    #[test]
+    fn located() {
+        assert_eq!(pairs("1,2\n3,4\n"), Ok(vec![(1, 2), (3, 4)]));
+
+        let err = pairs("1,2\n3;4\n5,6").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.snippet, "3;4");
+        assert_eq!(
+            err.to_string(),
+            "parse error at line 2, column 1: unexpected input\n2 | 3;4\n  | ^"
+        );
+    }
+    #[test]
+    fn context_labels() {
+        let pair = line("pair", separated_pair(u32, char(','), u32));
+        let err = parse_all("1,2\n3,4x", separated_list1(line_ending, pair)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+        assert_eq!(err.context, vec!["pair"]);
+        assert_eq!(
+            err.to_string(),
+            "parse error at line 2, column 4 in pair: unexpected input\n2 | 3,4x\n  |    ^"
+        );
+    }
+}
@@ -0,0 +1,255 @@
+//! Wall time and heap usage of each day's parse and parts.
+//!
+//! Heap figures come from [`CountingAlloc`], which only sees anything once a binary registers
+//! it as its `#[global_allocator]`; without it every allocation figure reads zero. The counters
+//! are process wide, so phases have to run one at a time to be measured separately.
+use crate::Day;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt,
+    io::{self, Write},
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering::Relaxed},
+    time::{Duration, Instant},
+};
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// The system allocator, keeping count of live bytes, their peak and allocation calls.
+pub struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Relaxed);
+    }
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            CURRENT.fetch_sub(layout.size(), Relaxed);
+            grow(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn grow(size: usize) {
+    ALLOCATIONS.fetch_add(1, Relaxed);
+    let current = CURRENT.fetch_add(size, Relaxed) + size;
+    PEAK.fetch_max(current, Relaxed);
+}
+
+/// What running a closure cost.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    pub wall: Duration,
+    /// Highest number of heap bytes live at once, on top of what was live before
+    pub peak_bytes: usize,
+    /// Allocation calls, reallocations included
+    pub allocations: u64,
+}
+
+/// Runs `f`, measuring its wall time and heap usage.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    let base = CURRENT.load(Relaxed);
+    PEAK.store(base, Relaxed);
+    let allocations = ALLOCATIONS.load(Relaxed);
+    let start = Instant::now();
+    let result = f();
+    let wall = start.elapsed();
+    let usage = Usage {
+        wall,
+        peak_bytes: PEAK.load(Relaxed).saturating_sub(base),
+        allocations: ALLOCATIONS.load(Relaxed) - allocations,
+    };
+    (result, usage)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The cost of one phase of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sample {
+    pub day: u8,
+    pub phase: Phase,
+    pub usage: Usage,
+}
+
+impl Day {
+    /// Parses `input` and answers the selected parts like [`Day::solve`], measuring each step.
+    pub fn profile(&self, input: &str, part: Option<u8>) -> anyhow::Result<Vec<Sample>> {
+        let (puzzle, usage) = measure(|| (self.parse)(input));
+        let puzzle = puzzle?;
+        let mut samples = vec![Sample {
+            day: self.day,
+            phase: Phase::Parse,
+            usage,
+        }];
+        for (phase, p) in [(Phase::Part1, 1), (Phase::Part2, 2)] {
+            if part.is_some_and(|part| part != p) {
+                continue;
+            }
+            let (answer, usage) = measure(|| {
+                if p == 1 {
+                    puzzle.part1()
+                } else {
+                    puzzle.part2()
+                }
+            });
+            answer?;
+            samples.push(Sample {
+                day: self.day,
+                phase,
+                usage,
+            });
+        }
+        Ok(samples)
+    }
+}
+
+/// Writes `samples` as a table, slowest first, followed by their total.
+pub fn write_table(out: &mut impl Write, samples: &[Sample]) -> io::Result<()> {
+    let mut samples = samples.to_vec();
+    samples.sort_by(|a, b| (b.usage.wall, a.day, a.phase).cmp(&(a.usage.wall, b.day, b.phase)));
+    let total = samples.iter().fold(Usage::default(), |acc, s| Usage {
+        wall: acc.wall + s.usage.wall,
+        peak_bytes: acc.peak_bytes.max(s.usage.peak_bytes),
+        allocations: acc.allocations + s.usage.allocations,
+    });
+    let row = |out: &mut dyn Write, day: &str, phase: &str, usage: &Usage| {
+        writeln!(
+            out,
+            "{:>3}  {:<5}  {:>11}  {:>6.1}%  {:>10}  {:>12}",
+            day,
+            phase,
+            duration(usage.wall),
+            100.0 * usage.wall.as_secs_f64() / total.wall.as_secs_f64().max(f64::MIN_POSITIVE),
+            bytes(usage.peak_bytes),
+            usage.allocations
+        )
+    };
+    writeln!(
+        out,
+        "{:>3}  {:<5}  {:>11}  {:>7}  {:>10}  {:>12}",
+        "day", "phase", "time", "share", "peak heap", "allocations"
+    )?;
+    for sample in &samples {
+        let day = format!("{:02}", sample.day);
+        row(out, &day, &sample.phase.to_string(), &sample.usage)?;
+    }
+    row(out, "all", "", &total)
+}
+
+fn duration(duration: Duration) -> String {
+    let micros = duration.as_secs_f64() * 1e6;
+    match micros {
+        m if m < 1e3 => format!("{:.1} µs", m),
+        m if m < 1e6 => format!("{:.2} ms", m / 1e3),
+        m => format!("{:.3} s", m / 1e6),
+    }
+}
+
+fn bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOC: CountingAlloc = CountingAlloc;
+
+    #[test]
+    fn measure_heap() {
+        let (len, usage) = measure(|| {
+            let big = vec![0u8; 64 << 20];
+            let small = Vec::from([1u64; 16]);
+            big.len() + small.len()
+        });
+        assert_eq!(len, (64 << 20) + 16);
+        // other tests allocate and free on their own threads meanwhile, so leave some slack
+        assert!(usage.peak_bytes >= 32 << 20);
+        assert!(usage.allocations >= 2);
+    }
+    #[test]
+    fn profile_day() -> anyhow::Result<()> {
+        let day = crate::day(1).expect("day 1");
+        let input = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263";
+        let samples = day.profile(input, Some(2))?;
+        let phases: Vec<_> = samples.iter().map(|s| (s.day, s.phase)).collect();
+        assert_eq!(phases, [(1, Phase::Parse), (1, Phase::Part2)]);
+        assert!(day.profile("199\nx", None).is_err());
+        Ok(())
+    }
+    #[test]
+    fn table() -> io::Result<()> {
+        let sample = |day, phase, millis, peak_bytes, allocations| Sample {
+            day,
+            phase,
+            usage: Usage {
+                wall: Duration::from_millis(millis),
+                peak_bytes,
+                allocations,
+            },
+        };
+        let samples = [
+            sample(1, Phase::Parse, 1, 100, 3),
+            sample(23, Phase::Part2, 3000, 5 << 30, 1_000_000),
+            sample(12, Phase::Part2, 999, 3 << 19, 50),
+        ];
+        let mut out = Vec::new();
+        write_table(&mut out, &samples)?;
+        assert_eq!(
+            String::from_utf8_lossy(&out),
+            "\
+day  phase         time    share   peak heap   allocations
+ 23  part2      3.000 s    75.0%     5.0 GiB       1000000
+ 12  part2    999.00 ms    25.0%     1.5 MiB            50
+ 01  parse      1.00 ms     0.0%       100 B             3
+all             4.000 s   100.0%     5.0 GiB       1000053
+"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,268 @@
+//! Draws the grid based simulations, as terminal frames or as images.
+use crate::{day11, day15, day20, day25, grid::Grid};
+use anyhow::{bail, ensure, Context};
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::Path,
+    thread,
+    time::Duration,
+};
+
+pub type Rgb = [u8; 3];
+
+/// One cell as drawn: a character for the terminal and a colour for images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pixel {
+    pub glyph: char,
+    pub colour: Rgb,
+}
+
+pub type Frame = Grid<Pixel>;
+
+/// Something that can be drawn as a single frame.
+pub trait Render {
+    fn render(&self) -> Frame;
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum RenderFormat {
+    /// Every frame as plain text, separated by blank lines
+    #[default]
+    Text,
+    /// Coloured frames redrawn in place in the terminal
+    Ansi,
+    /// One binary PPM image per frame
+    Ppm,
+    /// One PNG image per frame
+    Png,
+    /// A single animated GIF
+    Gif,
+}
+
+/// The frames of a day's simulation, with `part` picking between the two parts' setups.
+pub fn frames(day: u8, input: &str, part: u8) -> anyhow::Result<Box<dyn Iterator<Item = Frame>>> {
+    let frames: Box<dyn Iterator<Item = Frame>> = match day {
+        11 => {
+            let states = day11::parse(input)?.states();
+            if part == 1 {
+                Box::new(states.take(101).map(|sim| sim.render()))
+            } else {
+                let states = states.take(day11::MAX_STEPS as usize + 1);
+                Box::new(states.map(|sim| sim.render()))
+            }
+        }
+        15 => {
+            let risks = day15::parse(input)?;
+            let risks = if part == 1 {
+                risks
+            } else {
+                day15::tiled(&risks, 5)
+            };
+            let path = day15::best_path(&risks).context("no path through the cave")?;
+            Box::new(std::iter::once(
+                day15::PathOverlay::new(&risks, &path).render(),
+            ))
+        }
+        20 => {
+            let runs = if part == 1 { 2 } else { 50 };
+            let passes = day20::enhancement_passes(day20::parse(input.trim())?, runs);
+            Box::new(passes.map(|image| image.render()))
+        }
+        25 => {
+            let states = day25::parse(input.trim())?.iter();
+            let states = states.take(day25::MAX_STEPS + 1);
+            Box::new(states.map(|map| map.render()))
+        }
+        _ => bail!("day {} has nothing to render, try 11, 15, 20 or 25", day),
+    };
+    Ok(frames)
+}
+
+pub fn write_text(out: &mut impl Write, frames: impl IntoIterator<Item = Frame>) -> io::Result<()> {
+    for (step, frame) in frames.into_iter().enumerate() {
+        writeln!(out, "step {}", step)?;
+        for row in frame.rows() {
+            writeln!(out, "{}", row.iter().map(|p| p.glyph).collect::<String>())?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// Redraws each frame in place with 24-bit colour escapes, waiting `delay` in between.
+pub fn write_ansi(
+    out: &mut impl Write,
+    frames: impl IntoIterator<Item = Frame>,
+    delay: Duration,
+) -> io::Result<()> {
+    for (step, frame) in frames.into_iter().enumerate() {
+        if step > 0 {
+            thread::sleep(delay);
+        }
+        let mut screen = format!("\x1b[H\x1b[2Jstep {}\n", step);
+        for row in frame.rows() {
+            for Pixel { glyph, colour } in row {
+                let [r, g, b] = colour;
+                screen.push_str(&format!("\x1b[38;2;{};{};{}m{}", r, g, b, glyph));
+            }
+            screen.push_str("\x1b[0m\n");
+        }
+        out.write_all(screen.as_bytes())?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+/// The frame's colours as RGB bytes, each cell blown up to `scale` x `scale` pixels.
+fn scaled(frame: &Frame, scale: usize) -> Vec<Rgb> {
+    frame
+        .rows()
+        .flat_map(|row| {
+            let line = row
+                .iter()
+                .flat_map(|p| std::iter::repeat_n(p.colour, scale))
+                .collect::<Vec<_>>();
+            std::iter::repeat_n(line, scale).flatten()
+        })
+        .collect()
+}
+
+pub fn write_ppm(out: &mut impl Write, frame: &Frame, scale: usize) -> io::Result<()> {
+    let (w, h) = (frame.width() * scale, frame.height() * scale);
+    write!(out, "P6\n{} {}\n255\n", w, h)?;
+    out.write_all(&scaled(frame, scale).concat())
+}
+
+pub fn write_png(out: impl Write, frame: &Frame, scale: usize) -> anyhow::Result<()> {
+    let (w, h) = (frame.width() * scale, frame.height() * scale);
+    let mut encoder = png::Encoder::new(out, w.try_into()?, h.try_into()?);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&scaled(frame, scale).concat())?;
+    Ok(writer.finish()?)
+}
+
+/// Writes a looping GIF, centring smaller frames on the largest one.
+pub fn write_gif(
+    out: impl Write,
+    frames: impl IntoIterator<Item = Frame>,
+    scale: usize,
+    delay: Duration,
+) -> anyhow::Result<()> {
+    let frames: Vec<Frame> = frames.into_iter().collect();
+    let width = frames.iter().map(|f| f.width() * scale).max().unwrap_or(0);
+    let height = frames.iter().map(|f| f.height() * scale).max().unwrap_or(0);
+    let mut encoder = gif::Encoder::new(out, width.try_into()?, height.try_into()?, &[])?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+    for frame in &frames {
+        let (w, h) = (frame.width() * scale, frame.height() * scale);
+        let mut palette: HashMap<Rgb, u8> = HashMap::new();
+        let mut indices = Vec::with_capacity(w * h);
+        for colour in scaled(frame, scale) {
+            let next = palette.len();
+            let index = *palette.entry(colour).or_insert(next as u8);
+            ensure!(
+                palette.len() <= 256,
+                "a GIF frame can only have 256 colours"
+            );
+            indices.push(index);
+        }
+        let mut colours = vec![[0; 3]; palette.len()];
+        palette
+            .into_iter()
+            .for_each(|(c, i)| colours[i as usize] = c);
+        let mut gif_frame =
+            gif::Frame::from_palette_pixels(w as u16, h as u16, indices, colours.concat(), None);
+        gif_frame.left = ((width - w) / 2) as u16;
+        gif_frame.top = ((height - h) / 2) as u16;
+        gif_frame.delay = (delay.as_millis() / 10).try_into()?;
+        gif_frame.dispose = gif::DisposalMethod::Background;
+        encoder.write_frame(&gif_frame)?;
+    }
+    Ok(())
+}
+
+/// Writes `frames` to `output` in `format`; PPM and PNG go to numbered files in the `output`
+/// directory, the text formats to stdout if there's no `output`.
+pub fn export(
+    frames: impl Iterator<Item = Frame>,
+    format: RenderFormat,
+    output: Option<&Path>,
+    scale: usize,
+    delay: Duration,
+) -> anyhow::Result<()> {
+    let create = |path: &Path| {
+        File::create(path)
+            .map(BufWriter::new)
+            .with_context(|| format!("failed to create {}", path.display()))
+    };
+    match (format, output) {
+        (RenderFormat::Text, None) => write_text(&mut io::stdout().lock(), frames)?,
+        (RenderFormat::Text, Some(path)) => write_text(&mut create(path)?, frames)?,
+        (RenderFormat::Ansi, None) => write_ansi(&mut io::stdout().lock(), frames, delay)?,
+        (RenderFormat::Ansi, Some(path)) => write_ansi(&mut create(path)?, frames, delay)?,
+        (RenderFormat::Gif, Some(path)) => write_gif(create(path)?, frames, scale, delay)?,
+        (RenderFormat::Ppm | RenderFormat::Png, Some(dir)) => {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("failed to create {}", dir.display()))?;
+            for (step, frame) in frames.enumerate() {
+                if format == RenderFormat::Ppm {
+                    let mut out = create(&dir.join(format!("frame-{:04}.ppm", step)))?;
+                    write_ppm(&mut out, &frame, scale)?;
+                    out.flush()?;
+                } else {
+                    write_png(
+                        create(&dir.join(format!("frame-{:04}.png", step)))?,
+                        &frame,
+                        scale,
+                    )?;
+                }
+            }
+        }
+        (_, None) => bail!("image formats need an --output path"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> Frame {
+        Grid::from_fn(2, 1, |(x, _)| Pixel {
+            glyph: if x == 0 { '#' } else { '.' },
+            colour: [x as u8 * 255; 3],
+        })
+    }
+
+    #[test]
+    fn text() -> io::Result<()> {
+        let mut out = Vec::new();
+        write_text(&mut out, [frame(), frame()])?;
+        assert_eq!(
+            String::from_utf8_lossy(&out),
+            "step 0\n#.\n\nstep 1\n#.\n\n"
+        );
+        Ok(())
+    }
+    #[test]
+    fn images() -> anyhow::Result<()> {
+        let mut ppm = Vec::new();
+        write_ppm(&mut ppm, &frame(), 2)?;
+        assert!(ppm.starts_with(b"P6\n4 2\n255\n"));
+        assert_eq!(ppm.len(), 11 + 4 * 2 * 3);
+        assert_eq!(&ppm[11..17], &[0; 6]);
+
+        let mut png = Vec::new();
+        write_png(&mut png, &frame(), 2)?;
+        assert!(png.starts_with(b"\x89PNG"));
+
+        let mut gif = Vec::new();
+        write_gif(&mut gif, [frame(), frame()], 1, Duration::from_millis(100))?;
+        assert!(gif.starts_with(b"GIF89a"));
+        Ok(())
+    }
+}